@@ -2,14 +2,13 @@ use crate::{
     constants::{player_key, PLAYER_EXPIRY, PLAYER_STATS_KEY},
     metrics::{PLAYED_TRACKS, VOICE_CLOSES},
     models::{ApiResult, Player},
-    utils::decode_track,
 };
 
 use redis::AsyncCommands;
 use serde::{de::DeserializeOwned, Serialize};
 use std::time::Duration;
 use tracing::warn;
-use twilight_andesite::model::IncomingEvent;
+use twilight_andesite::{model::IncomingEvent, track};
 
 pub async fn get<T: DeserializeOwned>(
     conn: &mut redis::aio::Connection,
@@ -72,13 +71,10 @@ pub async fn update(conn: &mut redis::aio::Connection, event: &IncomingEvent) ->
         IncomingEvent::Stats(data) => {
             set(conn, PLAYER_STATS_KEY, data).await?;
         }
-        IncomingEvent::TrackStart(data) => match decode_track(data.track.clone()).await {
-            Ok(track) => {
+        IncomingEvent::TrackStart(data) => match track::decode(&data.track) {
+            Ok(info) => {
                 PLAYED_TRACKS
-                    .with_label_values(&[
-                        track.info.title.as_str(),
-                        track.info.length.to_string().as_str(),
-                    ])
+                    .with_label_values(&[info.title.as_str(), info.length.to_string().as_str()])
                     .inc();
             }
             Err(err) => {
@@ -87,7 +83,7 @@ pub async fn update(conn: &mut redis::aio::Connection, event: &IncomingEvent) ->
         },
         IncomingEvent::WebsocketClose(data) => {
             VOICE_CLOSES
-                .with_label_values(&[data.code.to_string().as_str()])
+                .with_label_values(&[data.code.code().to_string().as_str()])
                 .inc();
         }
         IncomingEvent::PlayerDestroy(data) => {
@@ -7,7 +7,9 @@ use lapin::{
 };
 use tracing::warn;
 use twilight_andesite::{
-    model::{Destroy, GetPlayer, IncomingEvent, OutgoingEvent, Play, Stop, Update, VoiceUpdate},
+    model::{
+        Destroy, GetPlayer, IncomingEvent, Mixer, OutgoingEvent, Play, Stop, Update, VoiceUpdate,
+    },
     node::Node,
 };
 
@@ -79,6 +81,8 @@ pub async fn incoming(node: &Node, mut consumer: Consumer) {
                             .map(OutgoingEvent::from),
                         "destroy" => simd_json::from_slice::<Destroy>(data.as_mut_slice())
                             .map(OutgoingEvent::from),
+                        "mixer" => simd_json::from_slice::<Mixer>(data.as_mut_slice())
+                            .map(OutgoingEvent::from),
                         _ => simd_json::from_slice::<OutgoingEvent>(data.as_mut_slice()),
                     },
                 ) {
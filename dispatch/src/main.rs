@@ -9,7 +9,7 @@ use crate::{
 
 use dotenv::dotenv;
 use lapin::{options::BasicConsumeOptions, types::FieldTable, ExchangeKind};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use twilight_andesite::Lavalink;
 use twilight_model::id::UserId;
 
@@ -67,7 +67,7 @@ async fn real_main() -> ApiResult<()> {
     });
 
     let config = get_node_config(&mut conn).await?;
-    let client = Lavalink::new(UserId(CONFIG.bot_id));
+    let client = Lavalink::new(UserId(CONFIG.bot_id), 1);
     let (node, receiver) = client
         .add_with_resume(config.address, config.authorization, config.resume)
         .await?;
@@ -76,6 +76,20 @@ async fn real_main() -> ApiResult<()> {
 
     cache::set(&mut conn, PLAYER_ID_KEY, &node.connection_id()).await?;
 
+    let mut conn_state = redis.get_async_connection().await?;
+    let node_state = node.clone();
+    tokio::spawn(async move {
+        let mut states = node_state.state_changes();
+        while states.changed().await.is_ok() {
+            if states.borrow().is_connected() {
+                let id = node_state.connection_id();
+                if let Err(err) = cache::set(&mut conn_state, PLAYER_ID_KEY, &id).await {
+                    warn!("Failed to update connection id: {:?}", err);
+                }
+            }
+        }
+    });
+
     let mut conn_clone = redis.get_async_connection().await?;
     tokio::spawn(async move {
         handler::outgoing(&mut conn_clone, receiver, channel).await;
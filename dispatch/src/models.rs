@@ -10,6 +10,7 @@ use std::{
     error::Error,
     fmt::{self, Display},
     net::AddrParseError,
+    num::TryFromIntError,
 };
 use twilight_andesite::{model::Filters, node::NodeError};
 use twilight_model::id::GuildId;
@@ -43,6 +44,7 @@ pub enum ApiError {
     PrometheusError(PrometheusError),
     ReqwestError(ReqwestError),
     NodeError(NodeError),
+    TryFromIntError(TryFromIntError),
 }
 
 impl Error for ApiError {}
@@ -112,3 +114,9 @@ impl From<NodeError> for ApiError {
         Self::NodeError(err)
     }
 }
+
+impl From<TryFromIntError> for ApiError {
+    fn from(err: TryFromIntError) -> Self {
+        Self::TryFromIntError(err)
+    }
+}
@@ -10,11 +10,8 @@ use lapin::{
     types::FieldTable,
     ExchangeKind,
 };
-use std::{convert::TryInto, net::SocketAddr, str::FromStr};
-use twilight_andesite::{
-    http::Track,
-    node::{NodeConfig, Resume},
-};
+use std::convert::TryFrom;
+use twilight_andesite::node::{NodeAddress, NodeConfig, Resume};
 use twilight_model::id::UserId;
 
 pub async fn exchange_declare(
@@ -77,12 +74,10 @@ pub async fn queue_bind(
     Ok(())
 }
 
-pub fn get_node_address() -> ApiResult<SocketAddr> {
-    let address = SocketAddr::from_str(
-        format!("{}:{}", CONFIG.andesite_host, CONFIG.andesite_port).as_str(),
-    )?;
+pub fn get_node_address() -> ApiResult<NodeAddress> {
+    let port = u16::try_from(CONFIG.andesite_port)?;
 
-    Ok(address)
+    Ok(NodeAddress::new(CONFIG.andesite_host.clone(), port, false))
 }
 
 pub async fn get_node_config(conn: &mut redis::aio::Connection) -> ApiResult<NodeConfig> {
@@ -90,25 +85,10 @@ pub async fn get_node_config(conn: &mut redis::aio::Connection) -> ApiResult<Nod
     let resume_id: Option<u64> = cache::get(conn, PLAYER_ID_KEY).await?;
     let resume = Resume::new_with_id(PLAYER_BUFFER as u64, resume_id);
 
-    Ok(NodeConfig {
+    Ok(NodeConfig::new(
+        UserId(CONFIG.bot_id),
         address,
-        authorization: CONFIG.andesite_secret.clone(),
-        resume: Some(resume),
-        user_id: UserId(CONFIG.bot_id),
-    })
-}
-
-pub async fn decode_track(track: String) -> ApiResult<Track> {
-    let address = get_node_address()?;
-    let config = NodeConfig {
-        address,
-        authorization: CONFIG.andesite_secret.clone(),
-        resume: None,
-        user_id: UserId(CONFIG.bot_id),
-    };
-
-    let request = twilight_andesite::http::decode_track(config, track)?;
-    let response = reqwest::Client::new().execute(request.try_into()?).await?;
-
-    Ok(response.json().await?)
+        CONFIG.andesite_secret.clone(),
+        resume,
+    ))
 }
@@ -1,31 +1,63 @@
 //! Client to manage nodes and players.
 
 use crate::{
-    model::{IncomingEvent, OutgoingEvent},
-    node::{Node, NodeConfig, NodeError, Resume},
-    player::{Player, PlayerManager},
+    model::{Destroy, IncomingEvent, VoiceCloseCode, VoiceUpdate, WebsocketClose},
+    node::{Node, NodeAddress, NodeConfig, NodeError, NodeState, Resume},
+    player::{Player, PlayerError, PlayerManager},
+    queue::QueueEvent,
+    selector::{NodeCandidate, NodeSelector, Penalty},
 };
-use dashmap::{mapref::one::Ref, DashMap};
-use futures_channel::mpsc::{TrySendError, UnboundedReceiver};
+use dashmap::{mapref::one::Ref, DashMap, DashSet};
+use futures_channel::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot::{self, Receiver, Sender},
+};
+use futures_util::stream::StreamExt;
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    net::SocketAddr,
-    sync::Arc,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::{sleep, sleep_until, timeout, Instant as TokioInstant, Sleep};
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{UpdateVoiceState, VoiceServerUpdate},
+    },
+    id::{ChannelId, GuildId, UserId},
 };
-use twilight_model::id::{GuildId, UserId};
+
+/// The default duration after which joining a voice channel times out.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// An error that can occur while interacting with the client.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Debug)]
 pub enum ClientError {
     /// A node isn't configured, so the operation isn't possible to fulfill.
     NodesUnconfigured,
-    /// Sending a voice update event to the node failed because the node's
-    /// connection was shutdown.
+    /// Sending a voice update event to the node failed because the node was
+    /// closed, or because it is reconnecting and its buffer is full.
     SendingVoiceUpdate {
         /// The source of the error.
-        source: TrySendError<OutgoingEvent>,
+        source: PlayerError,
     },
+    /// The bot left the voice channel before its voice update was forwarded
+    /// to a node.
+    VoiceConnectionCanceled,
+    /// Forwarding the voice update to a node failed. The cause is returned
+    /// from [`Lavalink::process`].
+    ///
+    /// [`Lavalink::process`]: struct.Lavalink.html#method.process
+    VoiceConnectionFailed,
+    /// The voice update wasn't forwarded to a node before the
+    /// [join timeout].
+    ///
+    /// [join timeout]: struct.Lavalink.html#method.set_join_timeout
+    VoiceConnectionTimedOut,
 }
 
 impl Display for ClientError {
@@ -33,6 +65,13 @@ impl Display for ClientError {
         match self {
             Self::NodesUnconfigured => f.write_str("no node has been configured"),
             Self::SendingVoiceUpdate { .. } => f.write_str("couldn't send voice update to node"),
+            Self::VoiceConnectionCanceled => {
+                f.write_str("voice connection was canceled before it was forwarded")
+            }
+            Self::VoiceConnectionFailed => f.write_str("voice connection couldn't be forwarded"),
+            Self::VoiceConnectionTimedOut => {
+                f.write_str("voice connection timed out before it was forwarded")
+            }
         }
     }
 }
@@ -42,16 +81,231 @@ impl Error for ClientError {
         match self {
             Self::NodesUnconfigured => None,
             Self::SendingVoiceUpdate { source } => Some(source),
+            Self::VoiceConnectionCanceled => None,
+            Self::VoiceConnectionFailed => None,
+            Self::VoiceConnectionTimedOut => None,
+        }
+    }
+}
+
+/// An event emitted by the client, received via [`Lavalink::events`].
+///
+/// [`Lavalink::events`]: struct.Lavalink.html#method.events
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClientEvent {
+    /// A player was moved to another node because its node disconnected
+    /// permanently, couldn't connect or reconnect for too long, or was
+    /// drained.
+    PlayerMigrated(PlayerMigrated),
+    /// A player couldn't be moved to another node because no other node was
+    /// available.
+    PlayerStranded(PlayerStranded),
+    /// The queue of a player changed.
+    Queue(QueueEvent),
+    /// The bot must join a voice channel again to recover its voice
+    /// connection, by sending the command over the shard with the given ID.
+    RejoinRequested(VoiceStateCommand),
+    /// Recovering a voice connection was given up after too many attempts.
+    VoiceRecoveryFailed(VoiceRecoveryFailed),
+}
+
+impl From<QueueEvent> for ClientEvent {
+    fn from(event: QueueEvent) -> Self {
+        Self::Queue(event)
+    }
+}
+
+/// A player was moved to another node because its node disconnected
+/// permanently, couldn't connect or reconnect for too long, or was [drained].
+///
+/// The voice connection was forwarded to the new node, and the track that was
+/// playing, if any, was restarted at its last known position.
+///
+/// [drained]: struct.Lavalink.html#method.drain
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerMigrated {
+    /// The guild ID of the player.
+    pub guild_id: GuildId,
+    /// The address of the node that the player was moved from.
+    pub from: NodeAddress,
+    /// The address of the node that the player was moved to.
+    pub to: NodeAddress,
+    /// The base64 track that was restarted, if any.
+    pub track: Option<String>,
+    /// The position in milliseconds that the track was restarted at.
+    pub position: Option<i64>,
+}
+
+/// A player couldn't be moved to another node because no other node was
+/// available.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerStranded {
+    /// The guild ID of the player.
+    pub guild_id: GuildId,
+    /// The address of the node that disconnected.
+    pub from: NodeAddress,
+}
+
+/// Recovering a voice connection was given up after too many attempts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoiceRecoveryFailed {
+    /// The guild ID of the player.
+    pub guild_id: GuildId,
+    /// The code that the voice connection was last closed with.
+    pub code: VoiceCloseCode,
+    /// The number of attempts that were made.
+    pub attempts: u32,
+}
+
+/// The policy for recovering voice connections that Discord closed.
+///
+/// When a node reports that Discord closed the voice connection of a player
+/// with a code that [should be reconnected], the client forwards the last voice
+/// update to the node again, or requests the bot to [rejoin] if a new session
+/// is required. The track that was playing is then restarted at its estimated
+/// position.
+///
+/// Recovery is disabled by default. Refer to
+/// [`Lavalink::set_voice_recovery`] to enable it.
+///
+/// [`Lavalink::set_voice_recovery`]: struct.Lavalink.html#method.set_voice_recovery
+/// [rejoin]: enum.ClientEvent.html#variant.RejoinRequested
+/// [should be reconnected]: ../model/incoming/enum.VoiceCloseCode.html#method.should_reconnect
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoiceRecovery {
+    /// The maximum number of consecutive attempts for a guild before giving
+    /// up.
+    pub max_attempts: u32,
+    /// The delay before each attempt.
+    pub delay: Duration,
+    /// The duration after the last attempt for a guild after which a new
+    /// attempt is no longer consecutive.
+    pub reset_after: Duration,
+}
+
+impl VoiceRecovery {
+    /// Configure voice recovery with the maximum number of consecutive
+    /// attempts for a guild.
+    ///
+    /// Attempts are made after a delay of 1 second, and are consecutive if
+    /// they are made within a minute of each other.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            delay: Duration::from_secs(1),
+            reset_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl Default for VoiceRecovery {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// The subscribers to a client's events.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Subscribers(Arc<Mutex<Vec<UnboundedSender<ClientEvent>>>>);
+
+impl Subscribers {
+    /// Create a new receiver of every event emitted from now on.
+    pub(crate) fn subscribe(&self) -> UnboundedReceiver<ClientEvent> {
+        let (tx, rx) = mpsc::unbounded();
+
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+
+        rx
+    }
+
+    /// Send an event to every subscriber, removing subscribers that dropped
+    /// their receiver.
+    pub(crate) fn emit(&self, event: impl Into<ClientEvent>) {
+        let event = event.into();
+        tracing::debug!("emitting client event: {:?}", event);
+
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|tx| tx.unbounded_send(event.clone()).is_ok());
+    }
+}
+
+/// A gateway command to update the bot's voice state, along with the ID of the
+/// shard that it must be sent over.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoiceStateCommand {
+    /// The ID of the shard that is responsible for the guild.
+    pub shard_id: u64,
+    /// The command to send over the shard.
+    pub command: UpdateVoiceState,
+}
+
+/// A future that resolves once the voice update for a guild has been forwarded
+/// to its node, created by [`Lavalink::join`].
+///
+/// Resolves to [`ClientError::VoiceConnectionCanceled`] if the bot leaves the
+/// voice channel first, to [`ClientError::VoiceConnectionFailed`] if the voice
+/// update couldn't be forwarded, and to
+/// [`ClientError::VoiceConnectionTimedOut`] if it wasn't forwarded before the
+/// [join timeout].
+///
+/// [`ClientError::VoiceConnectionCanceled`]: enum.ClientError.html#variant.VoiceConnectionCanceled
+/// [`ClientError::VoiceConnectionFailed`]: enum.ClientError.html#variant.VoiceConnectionFailed
+/// [`ClientError::VoiceConnectionTimedOut`]: enum.ClientError.html#variant.VoiceConnectionTimedOut
+/// [`Lavalink::join`]: struct.Lavalink.html#method.join
+/// [join timeout]: struct.Lavalink.html#method.set_join_timeout
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct VoiceConnected {
+    rx: Receiver<Result<(), ClientError>>,
+    deadline: Option<TokioInstant>,
+    timeout: Option<Pin<Box<Sleep>>>,
+}
+
+impl Future for VoiceConnected {
+    type Output = Result<(), ClientError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(res) = Pin::new(&mut self.rx).poll(cx) {
+            return Poll::Ready(res.unwrap_or(Err(ClientError::VoiceConnectionCanceled)));
+        }
+
+        // The timer is created once polled, because creating it requires a
+        // runtime.
+        if let Some(deadline) = self.deadline.take() {
+            self.timeout = Some(Box::pin(sleep_until(deadline)));
+        }
+
+        match self.timeout.as_mut() {
+            Some(timeout) => timeout
+                .as_mut()
+                .poll(cx)
+                .map(|()| Err(ClientError::VoiceConnectionTimedOut)),
+            None => Poll::Pending,
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct LavalinkRef {
-    guilds: DashMap<GuildId, SocketAddr>,
-    nodes: DashMap<SocketAddr, Node>,
+    draining: DashSet<NodeAddress>,
+    join_timeout: Mutex<Option<Duration>>,
+    nodes: DashMap<NodeAddress, Node>,
     players: PlayerManager,
+    recovery: Mutex<Option<VoiceRecovery>>,
+    recovery_attempts: DashMap<GuildId, (u32, Instant)>,
+    rejoining: DashSet<GuildId>,
+    selector: Box<dyn NodeSelector>,
+    server_updates: DashMap<GuildId, VoiceServerUpdate>,
+    sessions: DashMap<GuildId, String>,
+    shard_count: u64,
     user_id: UserId,
+    voice_states: DashMap<GuildId, UpdateVoiceState>,
+    waiters: DashMap<GuildId, Vec<Sender<Result<(), ClientError>>>>,
 }
 
 /// The lavalink client that manages nodes, players, and processes events from
@@ -66,9 +320,21 @@ struct LavalinkRef {
 /// information about the active playing information of a guild and allows you to send events to the
 /// connected node, such as [`Play`] events.
 ///
+/// If a node added via the client disconnects permanently, or has been
+/// connecting or reconnecting for longer than its [failover duration], its
+/// players are automatically moved to the [best] remaining nodes, and a
+/// [`ClientEvent`] is emitted for each of them via [`events`]. Voice
+/// connections that Discord closed can also be recovered automatically by
+/// setting a [voice recovery policy].
+///
+/// [`ClientEvent`]: enum.ClientEvent.html
 /// [`Play`]: ../model/outgoing/struct.Play.html
+/// [`events`]: #method.events
 /// [`player`]: #method.player
 /// [`process`]: #method.process
+/// [best]: #method.best
+/// [failover duration]: ../node/struct.NodeConfig.html#structfield.failover_after
+/// [voice recovery policy]: #method.set_voice_recovery
 #[derive(Clone, Debug)]
 pub struct Lavalink(Arc<LavalinkRef>);
 
@@ -76,26 +342,357 @@ impl Lavalink {
     /// Create a new Lavalink client instance.
     ///
     /// The user ID and number of shards provided may not be modified during
-    /// runtime, and the client must be re-created. These parameters are
-    /// automatically passed to new nodes created via [`add`].
+    /// runtime, and the client must be re-created. The user ID is
+    /// automatically passed to new nodes created via [`add`], and the number
+    /// of shards is used to calculate which shard a guild's voice state
+    /// commands must be sent over.
     ///
     /// [`add`]: #method.add
-    pub fn new(user_id: UserId) -> Self {
+    pub fn new(user_id: UserId, shard_count: u64) -> Self {
+        Self::new_with_selector(user_id, shard_count, Penalty)
+    }
+
+    /// Create a new Lavalink client instance with a custom strategy for
+    /// selecting the [best] node for new players.
+    ///
+    /// Refer to the [`selector`] module for the built-in strategies.
+    ///
+    /// [`selector`]: ../selector/index.html
+    /// [best]: #method.best
+    pub fn new_with_selector(
+        user_id: UserId,
+        shard_count: u64,
+        selector: impl NodeSelector + 'static,
+    ) -> Self {
         Self(Arc::new(LavalinkRef {
-            guilds: DashMap::new(),
+            draining: DashSet::new(),
+            join_timeout: Mutex::new(Some(JOIN_TIMEOUT)),
             nodes: DashMap::new(),
             players: PlayerManager::new(),
+            recovery: Mutex::new(None),
+            recovery_attempts: DashMap::new(),
+            rejoining: DashSet::new(),
+            selector: Box::new(selector),
+            server_updates: DashMap::new(),
+            sessions: DashMap::new(),
+            shard_count,
             user_id,
+            voice_states: DashMap::new(),
+            waiters: DashMap::new(),
         }))
     }
 
+    /// Retrieve the number of shards the client was created with.
+    pub fn shard_count(&self) -> u64 {
+        self.0.shard_count
+    }
+
+    /// Calculate the ID of the shard that is responsible for a guild.
+    pub fn shard_id(&self, guild_id: GuildId) -> u64 {
+        (guild_id.0 >> 22) % self.0.shard_count.max(1)
+    }
+
+    /// Create the gateway command to join a voice channel.
+    ///
+    /// The returned command must be sent over the shard with the given ID. The
+    /// returned future resolves once the resulting voice update has been
+    /// forwarded to the guild's node by [`process`], after which the player is
+    /// ready to play tracks. It resolves to an error if forwarding the voice
+    /// update failed or [timed out].
+    ///
+    /// # Examples
+    ///
+    /// Join a channel, then play a track:
+    ///
+    /// ```no_run
+    /// use twilight_andesite::{model::Play, Lavalink};
+    /// use twilight_gateway::Cluster;
+    /// # use twilight_model::id::{ChannelId, GuildId, UserId};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// # let (guild_id, channel_id, user_id) = (GuildId(1), ChannelId(2), UserId(3));
+    /// # let cluster: Cluster = unimplemented!();
+    /// # let track = String::new();
+    ///
+    /// let lavalink = Lavalink::new(user_id, 1);
+    ///
+    /// let (join, connected) = lavalink.join(guild_id, channel_id);
+    /// cluster.command(join.shard_id, &join.command).await?;
+    /// connected.await?;
+    ///
+    /// lavalink.player(guild_id).await?.send(Play::new(guild_id, track))?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`process`]: #method.process
+    /// [timed out]: #method.set_join_timeout
+    pub fn join(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> (VoiceStateCommand, VoiceConnected) {
+        self.join_with_state(guild_id, channel_id, false, false)
+    }
+
+    /// Similar to [`join`], but allows you to specify whether the bot is
+    /// deafened or muted.
+    ///
+    /// [`join`]: #method.join
+    pub fn join_with_state(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        self_deaf: bool,
+        self_mute: bool,
+    ) -> (VoiceStateCommand, VoiceConnected) {
+        let (tx, rx) = oneshot::channel();
+        let mut waiters = self.0.waiters.entry(guild_id).or_default();
+        waiters.retain(|waiter| !waiter.is_canceled());
+        waiters.push(tx);
+        drop(waiters);
+
+        let deadline = self
+            .0
+            .join_timeout
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map(|timeout| TokioInstant::now() + timeout);
+
+        let command = VoiceStateCommand {
+            shard_id: self.shard_id(guild_id),
+            command: UpdateVoiceState::new(guild_id, channel_id, self_deaf, self_mute),
+        };
+
+        (
+            command,
+            VoiceConnected {
+                rx,
+                deadline,
+                timeout: None,
+            },
+        )
+    }
+
+    /// Set the duration after which the futures returned by [`join`] resolve
+    /// to [`ClientError::VoiceConnectionTimedOut`], or `None` to wait
+    /// indefinitely. Defaults to 10 seconds.
+    ///
+    /// [`ClientError::VoiceConnectionTimedOut`]: enum.ClientError.html#variant.VoiceConnectionTimedOut
+    /// [`join`]: #method.join
+    pub fn set_join_timeout(&self, timeout: impl Into<Option<Duration>>) {
+        *self
+            .0
+            .join_timeout
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = timeout.into();
+    }
+
+    /// Create the gateway command to leave the voice channel of a guild.
+    ///
+    /// The returned command must be sent over the shard with the given ID. The
+    /// guild's player is destroyed once [`process`] receives the resulting
+    /// voice state update.
+    ///
+    /// [`process`]: #method.process
+    pub fn leave(&self, guild_id: GuildId) -> VoiceStateCommand {
+        VoiceStateCommand {
+            shard_id: self.shard_id(guild_id),
+            command: UpdateVoiceState::new(guild_id, None, false, false),
+        }
+    }
+
+    /// Process an event from the Discord gateway.
+    ///
+    /// This must be called with every Voice State Update and Voice Server
+    /// Update event you receive. Voice State Updates for users other than the
+    /// bot are ignored, as are all other event types.
+    ///
+    /// Once both the session ID from a Voice State Update and the token and
+    /// endpoint from a Voice Server Update have been received for a guild, a
+    /// [`VoiceUpdate`] is sent to the guild's player, creating one on the
+    /// [best] node if needed. A Voice State Update without a channel means
+    /// that the bot left the channel, in which case the guild's player is
+    /// destroyed and removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::NodesUnconfigured`] if a player needs to be
+    /// created but no node has been configured via [`add`].
+    ///
+    /// Returns [`ClientError::SendingVoiceUpdate`] if the voice update
+    /// couldn't be sent to the node because its connection was shutdown.
+    ///
+    /// [`ClientError::NodesUnconfigured`]: enum.ClientError.html#variant.NodesUnconfigured
+    /// [`ClientError::SendingVoiceUpdate`]: enum.ClientError.html#variant.SendingVoiceUpdate
+    /// [`VoiceUpdate`]: ../model/outgoing/struct.VoiceUpdate.html
+    /// [`add`]: #method.add
+    /// [best]: #method.best
+    pub async fn process(&self, event: &Event) -> Result<(), ClientError> {
+        tracing::trace!("processing event: {:?}", event);
+
+        let guild_id = match event {
+            Event::VoiceServerUpdate(e) => {
+                let guild_id = match e.guild_id {
+                    Some(guild_id) => guild_id,
+                    None => {
+                        tracing::trace!("event has no guild ID: {:?}", e);
+
+                        return Ok(());
+                    }
+                };
+
+                self.0.server_updates.insert(guild_id, e.clone());
+
+                guild_id
+            }
+            Event::VoiceStateUpdate(e) => {
+                if e.0.user_id != self.0.user_id {
+                    tracing::trace!("got voice state update from another user");
+
+                    return Ok(());
+                }
+
+                let guild_id = match e.0.guild_id {
+                    Some(guild_id) => guild_id,
+                    None => {
+                        tracing::trace!("event has no guild ID: {:?}", e);
+
+                        return Ok(());
+                    }
+                };
+
+                if e.0.channel_id.is_none() {
+                    self.disconnect(guild_id);
+
+                    return Ok(());
+                }
+
+                self.0.sessions.insert(guild_id, e.0.session_id.clone());
+                self.0.voice_states.insert(
+                    guild_id,
+                    UpdateVoiceState::new(guild_id, e.0.channel_id, e.0.self_deaf, e.0.self_mute),
+                );
+
+                guild_id
+            }
+            _ => return Ok(()),
+        };
+
+        let update = match self.voice_update(guild_id) {
+            Some(update) => update,
+            None => {
+                tracing::debug!("guild {} is waiting for the other half", guild_id);
+
+                return Ok(());
+            }
+        };
+
+        let res = self.forward_voice_update(guild_id, update).await;
+
+        if let Some((_, waiters)) = self.0.waiters.remove(&guild_id) {
+            for waiter in waiters {
+                let _ = waiter.send(match res {
+                    Ok(()) => Ok(()),
+                    Err(_) => Err(ClientError::VoiceConnectionFailed),
+                });
+            }
+        }
+
+        res
+    }
+
+    /// Send a voice update to the player of a guild, creating one on the best
+    /// node if needed.
+    async fn forward_voice_update(
+        &self,
+        guild_id: GuildId,
+        update: VoiceUpdate,
+    ) -> Result<(), ClientError> {
+        let node = self.player(guild_id).await?.node().clone();
+        let mut player = self.0.players.get_or_insert(guild_id, node);
+
+        tracing::debug!("sending voice update for guild {}: {:?}", guild_id, update);
+        player
+            .send(update.clone())
+            .map_err(|source| ClientError::SendingVoiceUpdate { source })?;
+        *player.voice_update_mut() = Some(update);
+
+        if self.0.rejoining.remove(&guild_id).is_some() {
+            tracing::debug!(
+                "rejoined voice channel in guild {}, restoring player",
+                guild_id
+            );
+
+            if let Err(source) = player.restore() {
+                tracing::warn!("couldn't restore player in guild {}: {}", guild_id, source);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve the address of the node that a guild's voice connection was
+    /// last forwarded to, if any.
+    pub fn guild_node(&self, guild_id: GuildId) -> Option<NodeAddress> {
+        self.0
+            .players
+            .get(&guild_id)
+            .filter(|player| player.voice_update().is_some())
+            .map(|player| player.node().config().address.clone())
+    }
+
+    /// Create a voice update for a guild from the cached voice server update
+    /// and session, if both have been received.
+    fn voice_update(&self, guild_id: GuildId) -> Option<VoiceUpdate> {
+        let server = self.0.server_updates.get(&guild_id)?;
+        let session = self.0.sessions.get(&guild_id)?;
+
+        Some(VoiceUpdate::new(
+            guild_id,
+            session.value().as_str(),
+            From::from(server.value().clone()),
+        ))
+    }
+
+    /// Clear the voice state of a guild after the bot left its channel, and
+    /// destroy its player.
+    fn disconnect(&self, guild_id: GuildId) {
+        tracing::debug!(
+            "left voice channel in guild {}, destroying player",
+            guild_id
+        );
+
+        self.0.recovery_attempts.remove(&guild_id);
+        self.0.rejoining.remove(&guild_id);
+        self.0.server_updates.remove(&guild_id);
+        self.0.sessions.remove(&guild_id);
+        self.0.voice_states.remove(&guild_id);
+        self.0.waiters.remove(&guild_id);
+
+        if let Some((_, player)) = self.0.players.remove(&guild_id) {
+            // The node may have already been removed, in which case there is
+            // no player left to destroy.
+            let _ = player.send(Destroy::new(guild_id));
+        }
+    }
+
     /// Add a new node to be managed by the Lavalink client.
     ///
     /// If a node already exists with the provided address, then it will be
-    /// replaced.
+    /// replaced and [closed].
+    ///
+    /// The node connects in the background, so it is returned before it has
+    /// connected. Refer to [`Node::connect`] for more information.
+    ///
+    /// The address may be a socket address, or a [`NodeAddress`] parsed from
+    /// a URL to connect using a hostname, TLS, or a base path.
+    ///
+    /// [`NodeAddress`]: ../node/struct.NodeAddress.html
+    /// [`Node::connect`]: ../node/struct.Node.html#method.connect
+    /// [closed]: ../node/struct.Node.html#method.close
     pub async fn add(
         &self,
-        address: SocketAddr,
+        address: impl Into<NodeAddress>,
         authorization: impl Into<String>,
     ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
         self.add_with_resume(address, authorization, None).await
@@ -106,66 +703,465 @@ impl Lavalink {
     /// [`add`]: #method.add
     pub async fn add_with_resume(
         &self,
-        address: SocketAddr,
+        address: impl Into<NodeAddress>,
         authorization: impl Into<String>,
         resume: impl Into<Option<Resume>>,
     ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
-        let config = NodeConfig {
+        self.add_with_config(NodeConfig::new(
+            self.0.user_id,
             address,
-            authorization: authorization.into(),
-            resume: resume.into(),
-            user_id: self.0.user_id,
-        };
+            authorization,
+            resume,
+        ))
+        .await
+    }
+
+    /// Similar to [`add`], but allows you to provide the whole configuration
+    /// of the node, such as its [reconnect policy] or regions.
+    ///
+    /// The user ID of the configuration is replaced with the client's.
+    ///
+    /// [`add`]: #method.add
+    /// [reconnect policy]: ../node/struct.Reconnect.html
+    pub async fn add_with_config(
+        &self,
+        mut config: NodeConfig,
+    ) -> Result<(Node, UnboundedReceiver<IncomingEvent>), NodeError> {
+        config.user_id = self.0.user_id;
+        let address = config.address.clone();
+        let failover_after = config.failover_after;
+
+        let (node, mut node_rx) = Node::connect(config, self.0.players.clone()).await?;
+
+        if let Some(replaced) = self.0.nodes.insert(address.clone(), node.clone()) {
+            replaced.close();
+        }
+
+        self.0.draining.remove(&address);
+
+        let (tx, rx) = mpsc::unbounded();
+        let lavalink = Arc::downgrade(&self.0);
+
+        tokio::spawn(async move {
+            while let Some(event) = node_rx.next().await {
+                if let IncomingEvent::WebsocketClose(close) = &event {
+                    if let Some(lavalink) = lavalink.upgrade() {
+                        Lavalink(lavalink).voice_closed(close);
+                    }
+                }
+
+                // It's fine if the rx end dropped, often users don't need to
+                // care about these events.
+                let _ = tx.unbounded_send(event);
+            }
+        });
+
+        let lavalink = Arc::downgrade(&self.0);
+        let mut states = node.state_changes();
+
+        tokio::spawn(async move {
+            loop {
+                let state = *states.borrow();
+
+                if state == NodeState::Disconnected {
+                    break;
+                }
 
-        let (node, rx) = Node::connect(config, self.0.players.clone()).await?;
-        self.0.nodes.insert(address, node.clone());
+                let changed = match failover_after {
+                    Some(duration) if !state.is_connected() => {
+                        timeout(duration, states.changed()).await
+                    }
+                    _ => Ok(states.changed().await),
+                };
+
+                match changed {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => {
+                        if *states.borrow() != NodeState::Disconnected {
+                            return;
+                        }
+
+                        break;
+                    }
+                    // The node has been connecting for too long, so move its
+                    // players away and wait for it to connect or give up.
+                    Err(_) => {
+                        match lavalink.upgrade() {
+                            Some(lavalink) => Lavalink(lavalink).fail_over(&address).await,
+                            None => return,
+                        }
+
+                        if states.changed().await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            if let Some(lavalink) = lavalink.upgrade() {
+                Lavalink(lavalink).fail_over(&address).await;
+            }
+        });
 
         Ok((node, rx))
     }
 
-    /// Get a node with the socket address.
-    pub fn get(&self, address: SocketAddr) -> Option<Node> {
-        if let Some(node) = self.0.nodes.get(&address) {
-            Some(node.clone())
-        } else {
-            None
+    /// Set the policy for recovering voice connections that Discord closed,
+    /// or `None` to disable recovery.
+    ///
+    /// Refer to [`VoiceRecovery`] for more information.
+    ///
+    /// [`VoiceRecovery`]: struct.VoiceRecovery.html
+    pub fn set_voice_recovery(&self, recovery: impl Into<Option<VoiceRecovery>>) {
+        *self
+            .0
+            .recovery
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = recovery.into();
+    }
+
+    /// Attempt to recover a voice connection that Discord closed, according
+    /// to the voice recovery policy.
+    fn voice_closed(&self, close: &WebsocketClose) {
+        let recovery = match self
+            .0
+            .recovery
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+        {
+            Some(recovery) => recovery,
+            None => return,
+        };
+
+        let guild_id = close.guild_id;
+
+        // Connections that the node closed itself, such as when its player
+        // was destroyed, aren't recovered.
+        if !close.by_remote || !close.code.should_reconnect() {
+            tracing::debug!(
+                "voice connection in guild {} closed with {:?} (by remote: {}), not recovering",
+                guild_id,
+                close.code,
+                close.by_remote
+            );
+
+            return;
+        }
+
+        let attempts = {
+            let mut attempts = self
+                .0
+                .recovery_attempts
+                .entry(guild_id)
+                .or_insert((0, Instant::now()));
+
+            if attempts.1.elapsed() >= recovery.reset_after {
+                attempts.0 = 0;
+            }
+
+            attempts.0 += 1;
+            attempts.1 = Instant::now();
+
+            attempts.0
+        };
+
+        if attempts > recovery.max_attempts {
+            tracing::warn!(
+                "giving up recovering voice connection in guild {} after {} attempts",
+                guild_id,
+                recovery.max_attempts
+            );
+            self.0.recovery_attempts.remove(&guild_id);
+            self.0.rejoining.remove(&guild_id);
+            self.emit(ClientEvent::VoiceRecoveryFailed(VoiceRecoveryFailed {
+                guild_id,
+                code: close.code,
+                attempts: recovery.max_attempts,
+            }));
+
+            return;
+        }
+
+        let lavalink = self.clone();
+        let code = close.code;
+
+        tokio::spawn(async move {
+            sleep(recovery.delay).await;
+            lavalink.recover(guild_id, code);
+        });
+    }
+
+    /// Recover a voice connection by forwarding the last voice update again or
+    /// requesting the bot to rejoin, and restore its player.
+    fn recover(&self, guild_id: GuildId, code: VoiceCloseCode) {
+        tracing::debug!(
+            "recovering voice connection in guild {} closed with {:?}",
+            guild_id,
+            code
+        );
+
+        if code.requires_rejoin() {
+            let command = match self.0.voice_states.get(&guild_id) {
+                Some(command) => command.value().clone(),
+                None => return,
+            };
+
+            self.0.rejoining.insert(guild_id);
+            self.emit(ClientEvent::RejoinRequested(VoiceStateCommand {
+                shard_id: self.shard_id(guild_id),
+                command,
+            }));
+
+            return;
+        }
+
+        if let Some(player) = self.0.players.get(&guild_id) {
+            if let Err(source) = player.reconnect() {
+                tracing::warn!("couldn't recover player in guild {}: {}", guild_id, source);
+            }
+        }
+    }
+
+    /// Drain a node, such as for maintenance.
+    ///
+    /// New players are no longer created on the node, and its existing
+    /// players are [moved] to the best remaining nodes with their playback
+    /// state preserved, emitting a [`ClientEvent::PlayerMigrated`] for each.
+    /// Finally, the node is removed from the client and its connection is
+    /// [closed].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::NodesUnconfigured`] if no other node is
+    /// available for one of the players. The node keeps draining, and the
+    /// players that weren't moved yet stay on it. Drain the node again once
+    /// another node is available, or [`add`] it again to stop draining.
+    ///
+    /// [`ClientError::NodesUnconfigured`]: enum.ClientError.html#variant.NodesUnconfigured
+    /// [`ClientEvent::PlayerMigrated`]: enum.ClientEvent.html#variant.PlayerMigrated
+    /// [`add`]: #method.add
+    /// [closed]: ../node/struct.Node.html#method.close
+    /// [moved]: ../player/struct.Player.html#method.move_to
+    pub async fn drain(&self, address: impl Into<NodeAddress>) -> Result<(), ClientError> {
+        let address = address.into();
+        self.0.draining.insert(address.clone());
+
+        let guild_ids = self.guilds_on(|node| node.config().address == address);
+        tracing::debug!("draining {} players from node {}", guild_ids.len(), address);
+
+        for guild_id in guild_ids {
+            let node = self.best_for(self.endpoint(guild_id).as_deref()).await?;
+
+            self.move_player(guild_id, &address, node);
         }
+
+        if let Some((_, node)) = self.0.nodes.remove(&address) {
+            node.close();
+        }
+
+        self.0.draining.remove(&address);
+
+        Ok(())
+    }
+
+    /// Move the players of a node that disconnected permanently, or has been
+    /// connecting or reconnecting for too long, to the best available nodes.
+    async fn fail_over(&self, address: &NodeAddress) {
+        let guild_ids = self
+            .guilds_on(|node| node.config().address == *address && !node.state().is_connected());
+
+        if guild_ids.is_empty() {
+            return;
+        }
+
+        tracing::warn!(
+            "node {} is unavailable, migrating {} players",
+            address,
+            guild_ids.len()
+        );
+
+        // Don't select the node for its own players while it's reconnecting,
+        // unless it's already being drained.
+        let draining = self.0.draining.insert(address.clone());
+
+        for guild_id in guild_ids {
+            match self.best_for(self.endpoint(guild_id).as_deref()).await {
+                Ok(node) => self.move_player(guild_id, address, node),
+                Err(source) => {
+                    tracing::warn!("couldn't migrate player in guild {}: {}", guild_id, source);
+                    self.emit(ClientEvent::PlayerStranded(PlayerStranded {
+                        guild_id,
+                        from: address.clone(),
+                    }));
+                }
+            }
+        }
+
+        if draining {
+            self.0.draining.remove(address);
+        }
+    }
+
+    /// Retrieve the IDs of the guilds whose player is linked to a matching
+    /// node.
+    fn guilds_on(&self, predicate: impl Fn(&Node) -> bool) -> Vec<GuildId> {
+        self.0
+            .players
+            .players
+            .iter()
+            .filter(|player| predicate(player.value().node()))
+            .map(|player| *player.key())
+            .collect()
+    }
+
+    /// Retrieve the endpoint of a guild's Discord voice server, if known.
+    fn endpoint(&self, guild_id: GuildId) -> Option<String> {
+        self.0
+            .server_updates
+            .get(&guild_id)
+            .and_then(|update| update.endpoint.clone())
+    }
+
+    /// Move a player to another node and notify subscribers.
+    fn move_player(&self, guild_id: GuildId, from: &NodeAddress, node: Node) {
+        let to = node.config().address.clone();
+
+        let (track, position) = {
+            let mut player = match self.0.players.get_mut(&guild_id) {
+                Some(player) => player,
+                None => return,
+            };
+
+            if let Err(source) = player.move_to(node) {
+                tracing::warn!("couldn't migrate player in guild {}: {}", guild_id, source);
+            }
+
+            (
+                player.current_track().map(|current| current.track),
+                player.position(),
+            )
+        };
+
+        self.emit(ClientEvent::PlayerMigrated(PlayerMigrated {
+            guild_id,
+            from: from.clone(),
+            to,
+            track,
+            position,
+        }));
+    }
+
+    /// Subscribe to events emitted by the client, such as players being
+    /// [migrated] to another node or changes to their [queue].
+    ///
+    /// Each call returns a new receiver that receives every event emitted
+    /// after it was created.
+    ///
+    /// [migrated]: enum.ClientEvent.html#variant.PlayerMigrated
+    /// [queue]: ../queue/index.html
+    pub fn events(&self) -> UnboundedReceiver<ClientEvent> {
+        self.0.players.subscribers.subscribe()
+    }
+
+    fn emit(&self, event: ClientEvent) {
+        self.0.players.subscribers.emit(event);
+    }
+
+    /// Get a node with the address.
+    pub fn get(&self, address: impl Into<NodeAddress>) -> Option<Node> {
+        self.0
+            .nodes
+            .get(&address.into())
+            .map(|node| node.value().clone())
     }
 
     /// Remove a node from the list of nodes being managed by the Lavalink
-    /// client.
+    /// client, and [close] it.
     ///
     /// The node is returned if it existed.
-    pub fn remove(&self, address: SocketAddr) -> Option<(SocketAddr, Node)> {
-        self.0.nodes.remove(&address)
+    ///
+    /// [close]: ../node/struct.Node.html#method.close
+    pub fn remove(&self, address: impl Into<NodeAddress>) -> Option<(NodeAddress, Node)> {
+        let removed = self.0.nodes.remove(&address.into());
+
+        if let Some((_, node)) = removed.as_ref() {
+            node.close();
+        }
+
+        removed
     }
 
-    /// Determine the "best" node for new players according to available nodes'
-    /// penalty scores.
+    /// Determine the "best" node for new players according to the client's
+    /// [node selector].
     ///
-    /// Refer to [`Node::penalty`] for how this is calculated.
+    /// Nodes whose connection has permanently disconnected and nodes that are
+    /// being [drained] are never chosen.
+    /// By default, the node with the lowest penalty score is chosen. Refer to
+    /// [`Node::penalty`] for how this is calculated.
     ///
     /// # Errors
     ///
     /// Returns [`ClientError::NodesUnconfigured`] if there are no configured
-    /// nodes available in the client.
+    /// nodes available in the client, or if the selector didn't select any of
+    /// them.
     ///
     /// [`ClientError::NodesUnconfigured`]: enum.ClientError.html#variant.NodesUnconfigured
     /// [`Node::penalty`]: ../node/struct.Node.html#method.penalty
+    /// [drained]: #method.drain
+    /// [node selector]: ../selector/trait.NodeSelector.html
     pub async fn best(&self) -> Result<Node, ClientError> {
-        let mut lowest = i32::MAX;
-        let mut best = None;
+        self.best_for(None).await
+    }
 
-        for node in self.0.nodes.iter() {
-            let penalty = node.value().penalty().await;
+    /// Determine the "best" node for a new player in a guild whose Discord
+    /// voice server has the given endpoint.
+    ///
+    /// Selectors such as [`RegionAware`] use the endpoint to prefer nodes
+    /// that are close to the voice server. Otherwise, this is the same as
+    /// [`best`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::NodesUnconfigured`] if there are no configured
+    /// nodes available in the client, or if the selector didn't select any of
+    /// them.
+    ///
+    /// [`ClientError::NodesUnconfigured`]: enum.ClientError.html#variant.NodesUnconfigured
+    /// [`RegionAware`]: ../selector/struct.RegionAware.html
+    /// [`best`]: #method.best
+    pub async fn best_for(&self, endpoint: Option<&str>) -> Result<Node, ClientError> {
+        let mut nodes = self
+            .0
+            .nodes
+            .iter()
+            .filter(|node| {
+                node.value().state() != NodeState::Disconnected
+                    && !self.0.draining.contains(node.key())
+            })
+            .map(|node| (node.key().to_string(), node.value().clone()))
+            .collect::<Vec<_>>();
+        nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
 
-            if penalty < lowest {
-                lowest = penalty;
-                best.replace(node.clone());
-            }
+        let mut candidates = Vec::with_capacity(nodes.len());
+
+        for (_, node) in nodes {
+            let stats = node.stats().await;
+
+            candidates.push(NodeCandidate { node, stats });
         }
 
-        best.ok_or(ClientError::NodesUnconfigured)
+        let index = self
+            .0
+            .selector
+            .select_for(&candidates, endpoint)
+            .ok_or(ClientError::NodesUnconfigured)?;
+
+        candidates
+            .into_iter()
+            .nth(index)
+            .map(|candidate| candidate.node)
+            .ok_or(ClientError::NodesUnconfigured)
     }
 
     /// Retrieve an immutable reference to the player manager.
@@ -179,6 +1175,9 @@ impl Lavalink {
     /// for the guild doesn't already exist. Use [`PlayerManager::get`] to only
     /// retrieve and not create.
     ///
+    /// If the guild's voice server is known, it is taken into account when
+    /// selecting the node. Refer to [`best_for`] for more information.
+    ///
     /// # Errors
     ///
     /// Returns [`ClientError::NodesUnconfigured`] if no node has been
@@ -187,13 +1186,175 @@ impl Lavalink {
     /// [`ClientError::NodesUnconfigured`]: enum.ClientError.html#variant.NodesUnconfigured
     /// [`PlayerManager::get`]: ../player/struct.PlayerManager.html#method.get
     /// [`add`]: #method.add
+    /// [`best_for`]: #method.best_for
     pub async fn player(&self, guild_id: GuildId) -> Result<Ref<'_, GuildId, Player>, ClientError> {
         if let Some(player) = self.players().get(&guild_id) {
             return Ok(player);
         }
 
-        let node = self.best().await?;
+        let node = self.best_for(self.endpoint(guild_id).as_deref()).await?;
 
         Ok(self.players().get_or_insert(guild_id, node).downgrade())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientError, ClientEvent, Lavalink, PlayerStranded, VoiceRecovery};
+    use crate::{
+        model::{Opcode, TrackEventType, VoiceCloseCode, WebsocketClose},
+        node::{NodeConfig, NodeState},
+    };
+    use futures_util::{future::FutureExt, stream::StreamExt};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::{VoiceServerUpdate, VoiceStateUpdate},
+        },
+        id::{ChannelId, GuildId, UserId},
+        voice::VoiceState,
+    };
+
+    const CHANNEL_ID: ChannelId = ChannelId(3);
+    const GUILD_ID: GuildId = GuildId(2);
+    const USER_ID: UserId = UserId(1);
+
+    fn server_update() -> Event {
+        Event::VoiceServerUpdate(VoiceServerUpdate {
+            channel_id: None,
+            endpoint: Some("endpoint".to_owned()),
+            guild_id: Some(GUILD_ID),
+            token: "token".to_owned(),
+        })
+    }
+
+    fn state_update() -> Event {
+        Event::VoiceStateUpdate(Box::new(VoiceStateUpdate(VoiceState {
+            channel_id: Some(CHANNEL_ID),
+            deaf: false,
+            guild_id: Some(GUILD_ID),
+            member: None,
+            mute: false,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: false,
+            session_id: "session".to_owned(),
+            suppress: false,
+            token: None,
+            user_id: USER_ID,
+            request_to_speak_timestamp: None,
+        })))
+    }
+
+    fn websocket_close(code: VoiceCloseCode, by_remote: bool) -> WebsocketClose {
+        WebsocketClose {
+            op: Opcode::Event,
+            kind: TrackEventType::WebsocketClose,
+            guild_id: GUILD_ID,
+            user_id: None,
+            reason: None,
+            code,
+            by_remote,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_voice_closed() {
+        let lavalink = Lavalink::new(USER_ID, 1);
+        lavalink.set_voice_recovery(VoiceRecovery::new(3));
+
+        lavalink.voice_closed(&websocket_close(VoiceCloseCode::VoiceServerCrashed, false));
+        lavalink.voice_closed(&websocket_close(VoiceCloseCode::Unknown(1000), true));
+        assert!(lavalink.0.recovery_attempts.get(&GUILD_ID).is_none());
+
+        lavalink.voice_closed(&websocket_close(VoiceCloseCode::VoiceServerCrashed, true));
+        assert!(lavalink.0.recovery_attempts.get(&GUILD_ID).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_connecting_node_fails_over() {
+        let lavalink = Lavalink::new(USER_ID, 1);
+
+        // Nothing listens on the port once the listener is dropped.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut config = NodeConfig::new(USER_ID, address, "", None);
+        config.failover_after = Some(Duration::from_millis(10));
+
+        let (node, _) = lavalink.add_with_config(config).await.unwrap();
+        assert_eq!(NodeState::Connecting, node.state());
+        lavalink.players().get_or_insert(GUILD_ID, node);
+
+        let mut events = lavalink.events();
+
+        assert!(matches!(
+            events.next().await,
+            Some(ClientEvent::PlayerStranded(PlayerStranded {
+                guild_id: GUILD_ID,
+                ..
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_join_failed() {
+        let lavalink = Lavalink::new(USER_ID, 1);
+        let (_, connected) = lavalink.join(GUILD_ID, CHANNEL_ID);
+
+        assert!(lavalink.process(&server_update()).await.is_ok());
+        assert!(matches!(
+            lavalink.process(&state_update()).await,
+            Err(ClientError::NodesUnconfigured)
+        ));
+        assert!(matches!(
+            connected.await,
+            Err(ClientError::VoiceConnectionFailed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_join_timed_out() {
+        let lavalink = Lavalink::new(USER_ID, 1);
+        lavalink.set_join_timeout(Duration::from_millis(10));
+        let (_, connected) = lavalink.join(GUILD_ID, CHANNEL_ID);
+
+        assert!(matches!(
+            connected.await,
+            Err(ClientError::VoiceConnectionTimedOut)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_join_uses_cached_server_update() {
+        let lavalink = Lavalink::new(USER_ID, 1);
+
+        // Nothing listens on the port once the listener is dropped, so events
+        // sent to the node are buffered.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        lavalink
+            .add_with_config(NodeConfig::new(USER_ID, address, "", None))
+            .await
+            .unwrap();
+
+        assert!(lavalink.process(&server_update()).await.is_ok());
+        assert!(lavalink.process(&state_update()).await.is_ok());
+
+        // Joining the channel that the bot is already in only results in a
+        // voice state update, which is forwarded with the cached server
+        // update.
+        let (_, connected) = lavalink.join(GUILD_ID, CHANNEL_ID);
+        assert_eq!(Some("endpoint".to_owned()), lavalink.endpoint(GUILD_ID));
+
+        assert!(lavalink.process(&state_update()).await.is_ok());
+        assert!(matches!(connected.now_or_never(), Some(Ok(()))));
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::node::NodeConfig;
 use http::{
-    header::{HeaderValue, AUTHORIZATION},
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE},
     Error as HttpError, Request,
 };
 use percent_encoding::NON_ALPHANUMERIC;
@@ -11,10 +11,11 @@ use serde::{Deserialize, Serialize};
 use twilight_model::id::GuildId;
 
 /// The type of search result given.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum LoadType {
     /// Loading the results failed.
+    #[default]
     LoadFailed,
     /// There were no matches.
     NoMatches,
@@ -26,12 +27,6 @@ pub enum LoadType {
     TrackLoaded,
 }
 
-impl Default for LoadType {
-    fn default() -> Self {
-        Self::LoadFailed
-    }
-}
-
 /// A track within a search result.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -126,10 +121,9 @@ pub fn load_track(
 ) -> Result<Request<&'static [u8]>, HttpError> {
     let identifier =
         percent_encoding::percent_encode(identifier.as_ref().as_bytes(), NON_ALPHANUMERIC);
-    let url = format!(
-        "http://{}/loadtracks?identifier={}",
-        config.address, identifier
-    );
+    let url = config
+        .address
+        .http_url(&format!("/loadtracks?identifier={}", identifier));
 
     let mut req = Request::get(url);
 
@@ -144,11 +138,10 @@ pub fn decode_track(
     config: NodeConfig,
     track: impl AsRef<str>,
 ) -> Result<Request<&'static [u8]>, HttpError> {
-    let url = format!(
-        "http://{}/decodetrack?track={}",
-        config.address,
-        track.as_ref()
-    );
+    let track = percent_encoding::percent_encode(track.as_ref().as_bytes(), NON_ALPHANUMERIC);
+    let url = config
+        .address
+        .http_url(&format!("/decodetrack?track={}", track));
 
     let mut req = Request::get(url);
 
@@ -158,9 +151,35 @@ pub fn decode_track(
     req.body(b"")
 }
 
+/// Decode multiple tracks based on their base64 encoded track strings.
+///
+/// The response will include a body which can be deserialized into a list of
+/// [`Track`]s.
+///
+/// [`Track`]: struct.Track.html
+pub fn decode_tracks(
+    config: NodeConfig,
+    tracks: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Request<Vec<u8>>, HttpError> {
+    let url = config.address.http_url("/decodetracks");
+    let tracks = tracks
+        .into_iter()
+        .map(|track| track.as_ref().to_owned())
+        .collect::<Vec<_>>();
+
+    let mut req = Request::post(url);
+
+    let auth_value = HeaderValue::from_str(config.authorization.as_ref())?;
+    req = req.header(AUTHORIZATION, auth_value);
+    req = req.header(CONTENT_TYPE, "application/json");
+
+    // Serializing a list of strings can't fail.
+    req.body(serde_json::to_vec(&tracks).unwrap_or_default())
+}
+
 /// Retrieve a player based on guild ID.
 pub fn get_player(config: NodeConfig, guild: GuildId) -> Result<Request<&'static [u8]>, HttpError> {
-    let url = format!("http://{}/player/{}", config.address, guild);
+    let url = config.address.http_url(&format!("/player/{}", guild));
 
     let mut req = Request::get(url);
 
@@ -170,3 +189,22 @@ pub fn get_player(config: NodeConfig, guild: GuildId) -> Result<Request<&'static
 
     req.body(b"")
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::node::NodeConfig;
+    use std::net::SocketAddr;
+    use twilight_model::id::UserId;
+
+    #[test]
+    fn test_decode_track_encodes_track() {
+        let address = SocketAddr::from(([127, 0, 0, 1], 2333));
+        let config = NodeConfig::new(UserId(1), address, "", None);
+        let req = super::decode_track(config, "QAAA+/w=").unwrap();
+
+        assert_eq!(
+            "http://127.0.0.1:2333/decodetrack?track=QAAA%2B%2Fw%3D",
+            req.uri().to_string()
+        );
+    }
+}
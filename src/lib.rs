@@ -26,6 +26,9 @@
 //! features. These features are mutually exclusive. `rustls` is enabled by
 //! default.
 //!
+//! TLS is used to connect to nodes whose [address] has an `https` or `wss`
+//! scheme.
+//!
 //! #### `native`
 //!
 //! The `native` feature enables [`async-tungstenite`]'s `tokio-native-tls`
@@ -62,7 +65,7 @@
 //! };
 //! use twilight_gateway::{Event, Intents, Shard};
 //! use twilight_http::Client as HttpClient;
-//! use twilight_lavalink::{http::LoadedTracks, model::Play, Lavalink};
+//! use twilight_andesite::{http::LoadedTracks, model::Play, Lavalink};
 //! use twilight_model::{
 //!     channel::Message,
 //!     gateway::payload::MessageCreate,
@@ -97,6 +100,7 @@
 //!
 //! [`Lavalink::process`]: client/struct.Lavalink.html#method.process
 //! [Lavalink]: https://github.com/Frederikam/Lavalink
+//! [address]: node/struct.NodeAddress.html
 //! [`async-tungstenite`]: https://crates.io/crates/async-tungstenite
 //! [`http`]: https://crates.io/crates/http
 //! [`rustls`]: https://crates.io/crates/rustls
@@ -120,14 +124,20 @@
     unused,
     warnings
 )]
-#![allow(clippy::large_enum_variant)]
+#![allow(clippy::large_enum_variant, clippy::result_large_err)]
 
 pub mod client;
 pub mod model;
 pub mod node;
 pub mod player;
+pub mod queue;
+pub mod selector;
+
+mod random;
 
 #[cfg(feature = "http")]
 pub mod http;
+#[cfg(feature = "http")]
+pub mod track;
 
 pub use self::{client::Lavalink, node::Node, player::PlayerManager};
@@ -20,6 +20,8 @@ pub enum Opcode {
     Update,
     /// Destroy a player.
     Destroy,
+    /// Enable or disable the mixer of a player.
+    Mixer,
     /// An update about a player's current track.
     PlayerUpdate,
     /// Meta information about a track starting or ending.
@@ -52,6 +54,8 @@ pub mod outgoing {
         Update(Update),
         /// Destroy a player for a guild.
         Destroy(Destroy),
+        /// Enable or disable the mixer of a player.
+        Mixer(Mixer),
     }
 
     impl OutgoingEvent {
@@ -64,6 +68,7 @@ pub mod outgoing {
                 OutgoingEvent::Stop(data) => data.op,
                 OutgoingEvent::Update(data) => data.op,
                 OutgoingEvent::Destroy(data) => data.op,
+                OutgoingEvent::Mixer(data) => data.op,
             }
         }
 
@@ -76,6 +81,7 @@ pub mod outgoing {
                 OutgoingEvent::Stop(data) => data.guild_id,
                 OutgoingEvent::Update(data) => data.guild_id,
                 OutgoingEvent::Destroy(data) => data.guild_id,
+                OutgoingEvent::Mixer(data) => data.guild_id,
             }
         }
     }
@@ -116,6 +122,12 @@ pub mod outgoing {
         }
     }
 
+    impl From<Mixer> for OutgoingEvent {
+        fn from(event: Mixer) -> OutgoingEvent {
+            Self::Mixer(event)
+        }
+    }
+
     /// A combined voice server and voice state update.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -199,7 +211,12 @@ pub mod outgoing {
         /// The position in milliseconds to start the track from.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub start_time: Option<u64>,
-        /// The position in milliseconds to end the track. Does nothing.
+        /// The position in milliseconds to end the track.
+        ///
+        /// This is enforced by the [`Player`] that sent the event, which stops
+        /// the track once it reaches the end time.
+        ///
+        /// [`Player`]: ../../player/struct.Player.html
         #[serde(skip_serializing_if = "Option::is_none")]
         pub end_time: Option<u64>,
         /// Whether or not to replace the currently playing track with this new
@@ -208,6 +225,9 @@ pub mod outgoing {
         /// Set to `true` to keep playing the current playing track, or `false`
         /// to replace the current playing track with a new one.
         pub no_replace: bool,
+        /// The ID of the mixer player to play the track on, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub player_id: Option<String>,
     }
 
     impl Play {
@@ -231,8 +251,18 @@ pub mod outgoing {
                 start_time: start_time.into(),
                 end_time: end_time.into(),
                 no_replace,
+                player_id: None,
             }
         }
+
+        /// Play the track on a mixer player instead of the main player.
+        ///
+        /// The mixer player is created if it doesn't exist yet.
+        pub fn mixer_player(mut self, player_id: impl Into<String>) -> Self {
+            self.player_id = Some(player_id.into());
+
+            self
+        }
     }
 
     /// Stop a player.
@@ -243,6 +273,9 @@ pub mod outgoing {
         pub op: Opcode,
         /// The guild ID of the player.
         pub guild_id: GuildId,
+        /// The ID of the mixer player to stop, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub player_id: Option<String>,
     }
 
     impl Stop {
@@ -251,13 +284,34 @@ pub mod outgoing {
             Self {
                 guild_id,
                 op: Opcode::Stop,
+                player_id: None,
             }
         }
+
+        /// Stop a mixer player instead of the main player.
+        pub fn mixer_player(mut self, player_id: impl Into<String>) -> Self {
+            self.player_id = Some(player_id.into());
+
+            self
+        }
     }
 
-    /// Set the filters of a player
+    /// The filters of a player.
+    ///
+    /// Filters that are `None` are unset, and aren't sent to the node, so
+    /// sending them in an [`Update`] leaves them unchanged. To reset a filter,
+    /// set it to its default, which doesn't alter the audio. [`reset`] returns
+    /// filters where every filter is reset.
+    ///
+    /// Whether a filter is enabled, which is the case when it alters the
+    /// audio, is derived from its values rather than sent to or taken from
+    /// the node. [`is_enabled`] returns it.
+    ///
+    /// [`Update`]: struct.Update.html
+    /// [`is_enabled`]: #method.is_enabled
+    /// [`reset`]: #method.reset
     #[skip_serializing_none]
-    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Filters {
         /// The karaoke filter.
@@ -270,9 +324,16 @@ pub mod outgoing {
         pub vibrato: Option<Vibrato>,
         /// The equalizer filter.
         pub equalizer: Option<Equalizer>,
-        /// The volume filter, always None.
-        #[serde(skip)]
-        pub volume: Option<()>,
+        /// The volume filter.
+        pub volume: Option<Volume>,
+        /// The rotation filter.
+        pub rotation: Option<Rotation>,
+        /// The distortion filter.
+        pub distortion: Option<Distortion>,
+        /// The channel mix filter.
+        pub channel_mix: Option<ChannelMix>,
+        /// The low-pass filter.
+        pub low_pass: Option<LowPass>,
     }
 
     impl Filters {
@@ -291,41 +352,248 @@ pub mod outgoing {
                 vibrato: vibrato.into(),
                 equalizer: equalizer.into(),
                 volume: None,
+                rotation: None,
+                distortion: None,
+                channel_mix: None,
+                low_pass: None,
             }
         }
-    }
 
-    impl Default for Filters {
-        fn default() -> Self {
+        /// Set the karaoke filter.
+        pub fn karaoke(mut self, karaoke: Karaoke) -> Self {
+            self.karaoke = Some(karaoke);
+
+            self
+        }
+
+        /// Set the timescale filter.
+        pub fn timescale(mut self, timescale: Timescale) -> Self {
+            self.timescale = Some(timescale);
+
+            self
+        }
+
+        /// Set the tremolo filter.
+        pub fn tremolo(mut self, tremolo: Tremolo) -> Self {
+            self.tremolo = Some(tremolo);
+
+            self
+        }
+
+        /// Set the vibrato filter.
+        pub fn vibrato(mut self, vibrato: Vibrato) -> Self {
+            self.vibrato = Some(vibrato);
+
+            self
+        }
+
+        /// Set the equalizer filter.
+        pub fn equalizer(mut self, equalizer: Equalizer) -> Self {
+            self.equalizer = Some(equalizer);
+
+            self
+        }
+
+        /// Set the volume filter.
+        pub fn volume(mut self, volume: Volume) -> Self {
+            self.volume = Some(volume);
+
+            self
+        }
+
+        /// Set the rotation filter.
+        pub fn rotation(mut self, rotation: Rotation) -> Self {
+            self.rotation = Some(rotation);
+
+            self
+        }
+
+        /// Set the distortion filter.
+        pub fn distortion(mut self, distortion: Distortion) -> Self {
+            self.distortion = Some(distortion);
+
+            self
+        }
+
+        /// Set the channel mix filter.
+        pub fn channel_mix(mut self, channel_mix: ChannelMix) -> Self {
+            self.channel_mix = Some(channel_mix);
+
+            self
+        }
+
+        /// Set the low-pass filter.
+        pub fn low_pass(mut self, low_pass: LowPass) -> Self {
+            self.low_pass = Some(low_pass);
+
+            self
+        }
+
+        /// Return the kinds of the filters that are set.
+        pub fn kinds(&self) -> Vec<FilterKind> {
+            FilterKind::ALL
+                .iter()
+                .copied()
+                .filter(|kind| self.is_set(*kind))
+                .collect()
+        }
+
+        /// Return whether a filter is set.
+        pub fn is_set(&self, kind: FilterKind) -> bool {
+            match kind {
+                FilterKind::Karaoke => self.karaoke.is_some(),
+                FilterKind::Timescale => self.timescale.is_some(),
+                FilterKind::Tremolo => self.tremolo.is_some(),
+                FilterKind::Vibrato => self.vibrato.is_some(),
+                FilterKind::Equalizer => self.equalizer.is_some(),
+                FilterKind::Volume => self.volume.is_some(),
+                FilterKind::Rotation => self.rotation.is_some(),
+                FilterKind::Distortion => self.distortion.is_some(),
+                FilterKind::ChannelMix => self.channel_mix.is_some(),
+                FilterKind::LowPass => self.low_pass.is_some(),
+            }
+        }
+
+        /// Return whether a filter is set and alters the audio.
+        pub fn is_enabled(&self, kind: FilterKind) -> bool {
+            match kind {
+                FilterKind::Karaoke => self.karaoke.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Timescale => self.timescale.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Tremolo => self.tremolo.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Vibrato => self.vibrato.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Equalizer => self.equalizer.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Volume => self.volume.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Rotation => self.rotation.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::Distortion => self.distortion.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::ChannelMix => self.channel_mix.as_ref().is_some_and(|f| f.enabled()),
+                FilterKind::LowPass => self.low_pass.as_ref().is_some_and(|f| f.enabled()),
+            }
+        }
+
+        /// Unset a filter.
+        pub fn unset(&mut self, kind: FilterKind) {
+            match kind {
+                FilterKind::Karaoke => self.karaoke = None,
+                FilterKind::Timescale => self.timescale = None,
+                FilterKind::Tremolo => self.tremolo = None,
+                FilterKind::Vibrato => self.vibrato = None,
+                FilterKind::Equalizer => self.equalizer = None,
+                FilterKind::Volume => self.volume = None,
+                FilterKind::Rotation => self.rotation = None,
+                FilterKind::Distortion => self.distortion = None,
+                FilterKind::ChannelMix => self.channel_mix = None,
+                FilterKind::LowPass => self.low_pass = None,
+            }
+        }
+
+        /// Create filters that reset every filter to its default.
+        ///
+        /// Every kind of filter is set, including those that a node may not
+        /// support. A [`Player`] leaves those out when sending the filters,
+        /// since their defaults don't alter the audio.
+        ///
+        /// [`Player`]: ../../player/struct.Player.html
+        pub fn reset() -> Self {
             Self::new(
-                Karaoke {
-                    level: 0.0,
-                    mono_level: 0.0,
-                    filter_band: 0.0,
-                    filter_width: 0.0,
-                    enabled: false,
-                },
-                Timescale {
-                    speed: 0.0,
-                    pitch: 0.0,
-                    rate: 0.0,
-                    enabled: false,
-                },
-                Tremolo {
-                    frequency: 0.0,
-                    depth: 0.0,
-                    enabled: false,
-                },
-                Vibrato {
-                    frequency: 0.0,
-                    depth: 0.0,
-                    enabled: false,
-                },
-                Equalizer {
-                    bands: vec![],
-                    enabled: false,
-                },
+                Karaoke::default(),
+                Timescale::default(),
+                Tremolo::default(),
+                Vibrato::default(),
+                Equalizer::default(),
             )
+            .volume(Volume::default())
+            .rotation(Rotation::default())
+            .distortion(Distortion::default())
+            .channel_mix(ChannelMix::default())
+            .low_pass(LowPass::default())
+        }
+
+        /// Apply the filters that are set in other filters, leaving the
+        /// others unchanged, like the node does when it receives them.
+        pub fn apply(&mut self, other: &Filters) {
+            fn apply<T: Clone>(filter: &mut Option<T>, other: &Option<T>) {
+                if other.is_some() {
+                    filter.clone_from(other);
+                }
+            }
+
+            apply(&mut self.karaoke, &other.karaoke);
+            apply(&mut self.timescale, &other.timescale);
+            apply(&mut self.tremolo, &other.tremolo);
+            apply(&mut self.vibrato, &other.vibrato);
+            apply(&mut self.equalizer, &other.equalizer);
+            apply(&mut self.volume, &other.volume);
+            apply(&mut self.rotation, &other.rotation);
+            apply(&mut self.distortion, &other.distortion);
+            apply(&mut self.channel_mix, &other.channel_mix);
+            apply(&mut self.low_pass, &other.low_pass);
+        }
+    }
+
+    /// A kind of filter.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum FilterKind {
+        /// The karaoke filter.
+        Karaoke,
+        /// The timescale filter.
+        Timescale,
+        /// The tremolo filter.
+        Tremolo,
+        /// The vibrato filter.
+        Vibrato,
+        /// The equalizer filter.
+        Equalizer,
+        /// The volume filter.
+        Volume,
+        /// The rotation filter.
+        Rotation,
+        /// The distortion filter.
+        Distortion,
+        /// The channel mix filter.
+        ChannelMix,
+        /// The low-pass filter.
+        LowPass,
+    }
+
+    impl FilterKind {
+        /// Every kind of filter.
+        pub const ALL: &'static [FilterKind] = &[
+            Self::Karaoke,
+            Self::Timescale,
+            Self::Tremolo,
+            Self::Vibrato,
+            Self::Equalizer,
+            Self::Volume,
+            Self::Rotation,
+            Self::Distortion,
+            Self::ChannelMix,
+            Self::LowPass,
+        ];
+
+        /// The kinds of filters that Andesite supports.
+        pub const ANDESITE: &'static [FilterKind] = &[
+            Self::Karaoke,
+            Self::Timescale,
+            Self::Tremolo,
+            Self::Vibrato,
+            Self::Equalizer,
+            Self::Volume,
+        ];
+
+        /// Retrieve the name of the filter as sent to the server.
+        pub fn as_str(self) -> &'static str {
+            match self {
+                Self::Karaoke => "karaoke",
+                Self::Timescale => "timescale",
+                Self::Tremolo => "tremolo",
+                Self::Vibrato => "vibrato",
+                Self::Equalizer => "equalizer",
+                Self::Volume => "volume",
+                Self::Rotation => "rotation",
+                Self::Distortion => "distortion",
+                Self::ChannelMix => "channelMix",
+                Self::LowPass => "lowPass",
+            }
         }
     }
 
@@ -341,9 +609,6 @@ pub mod outgoing {
         pub filter_band: f64,
         /// The filter width.
         pub filter_width: f64,
-        /// Whether is enabled, always false.
-        #[serde(skip)]
-        pub enabled: bool,
     }
 
     impl Karaoke {
@@ -354,7 +619,23 @@ pub mod outgoing {
                 mono_level,
                 filter_band,
                 filter_width,
-                enabled: false,
+            }
+        }
+
+        /// Return whether the filter alters the audio, which is the case when
+        /// either of its levels isn't 0.
+        pub fn enabled(&self) -> bool {
+            self.level != 0.0 || self.mono_level != 0.0
+        }
+    }
+
+    impl Default for Karaoke {
+        fn default() -> Self {
+            Self {
+                level: 0.0,
+                mono_level: 0.0,
+                filter_band: 220.0,
+                filter_width: 100.0,
             }
         }
     }
@@ -369,19 +650,26 @@ pub mod outgoing {
         pub pitch: f64,
         /// Rate to play at.
         pub rate: f64,
-        /// Whether is enabled, always false.
-        #[serde(skip)]
-        pub enabled: bool,
     }
 
     impl Timescale {
         /// Create a new timescale filter.
         pub fn new(speed: f64, pitch: f64, rate: f64) -> Self {
+            Self { speed, pitch, rate }
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            *self != Self::default()
+        }
+    }
+
+    impl Default for Timescale {
+        fn default() -> Self {
             Self {
-                speed,
-                pitch,
-                rate,
-                enabled: false,
+                speed: 1.0,
+                pitch: 1.0,
+                rate: 1.0,
             }
         }
     }
@@ -394,18 +682,26 @@ pub mod outgoing {
         pub frequency: f64,
         /// The filter depth.
         pub depth: f64,
-        /// Whether is enabled, always false.
-        #[serde(skip)]
-        pub enabled: bool,
     }
 
     impl Tremolo {
         /// Create a new tremolo filter.
         pub fn new(frequency: f64, depth: f64) -> Self {
+            Self { frequency, depth }
+        }
+
+        /// Return whether the filter alters the audio, which is the case when
+        /// it has a depth.
+        pub fn enabled(&self) -> bool {
+            self.depth != 0.0
+        }
+    }
+
+    impl Default for Tremolo {
+        fn default() -> Self {
             Self {
-                frequency,
-                depth,
-                enabled: false,
+                frequency: 2.0,
+                depth: 0.0,
             }
         }
     }
@@ -418,40 +714,50 @@ pub mod outgoing {
         pub frequency: f64,
         /// The filter depth.
         pub depth: f64,
-        /// Whether is enabled, always false.
-        #[serde(skip)]
-        pub enabled: bool,
     }
 
     impl Vibrato {
-        /// Create a new timescale filter.
+        /// Create a new vibrato filter.
         pub fn new(frequency: f64, depth: f64) -> Self {
+            Self { frequency, depth }
+        }
+
+        /// Return whether the filter alters the audio, which is the case when
+        /// it has a depth.
+        pub fn enabled(&self) -> bool {
+            self.depth != 0.0
+        }
+    }
+
+    impl Default for Vibrato {
+        fn default() -> Self {
             Self {
-                frequency,
-                depth,
-                enabled: false,
+                frequency: 2.0,
+                depth: 0.0,
             }
         }
     }
 
     /// Equalize a player.
-    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Equalizer {
         /// The bands to use as part of the equalizer.
+        ///
+        /// Bands that aren't included have a gain of 0.
         pub bands: Vec<EqualizerBand>,
-        /// Whether is enabled, always false.
-        #[serde(skip)]
-        pub enabled: bool,
     }
 
     impl Equalizer {
         /// Create a new equalizer filter.
         pub fn new(bands: Vec<EqualizerBand>) -> Self {
-            Self {
-                bands,
-                enabled: false,
-            }
+            Self { bands }
+        }
+
+        /// Return whether the filter alters the audio, which is the case when
+        /// any band has a gain.
+        pub fn enabled(&self) -> bool {
+            self.bands.iter().any(|band| band.gain != 0.0)
         }
     }
 
@@ -465,6 +771,266 @@ pub mod outgoing {
         pub gain: f64,
     }
 
+    /// Volume filter, which scales the volume of a player on top of its
+    /// volume.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Volume {
+        /// The factor to scale the volume by. 1 is the default.
+        pub volume: f64,
+    }
+
+    impl Volume {
+        /// Create a new volume filter.
+        pub fn new(volume: f64) -> Self {
+            Self { volume }
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            self.volume != 1.0
+        }
+    }
+
+    impl Default for Volume {
+        fn default() -> Self {
+            Self::new(1.0)
+        }
+    }
+
+    /// Rotation filter, which pans the audio around the listener, also known
+    /// as 8D audio.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Rotation {
+        /// The frequency in Hz of the rotation.
+        pub rotation_hz: f64,
+    }
+
+    impl Rotation {
+        /// Create a new rotation filter.
+        pub fn new(rotation_hz: f64) -> Self {
+            Self { rotation_hz }
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            self.rotation_hz != 0.0
+        }
+    }
+
+    impl Default for Rotation {
+        fn default() -> Self {
+            Self::new(0.0)
+        }
+    }
+
+    /// Distortion filter.
+    ///
+    /// Each sample is transformed by the sum of the scaled sine, cosine, and
+    /// tangent of it, each with their own offset and scale, which is then
+    /// offset and scaled itself. The default doesn't alter the audio.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Distortion {
+        /// The offset of the sine.
+        pub sin_offset: f64,
+        /// The scale of the sine.
+        pub sin_scale: f64,
+        /// The offset of the cosine.
+        pub cos_offset: f64,
+        /// The scale of the cosine.
+        pub cos_scale: f64,
+        /// The offset of the tangent.
+        pub tan_offset: f64,
+        /// The scale of the tangent.
+        pub tan_scale: f64,
+        /// The offset of the result.
+        pub offset: f64,
+        /// The scale of the result.
+        pub scale: f64,
+    }
+
+    impl Distortion {
+        /// Create a new distortion filter that doesn't alter the audio, to be
+        /// configured with the other methods.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Set the offset of the sine.
+        pub fn sin_offset(mut self, sin_offset: f64) -> Self {
+            self.sin_offset = sin_offset;
+
+            self
+        }
+
+        /// Set the scale of the sine.
+        pub fn sin_scale(mut self, sin_scale: f64) -> Self {
+            self.sin_scale = sin_scale;
+
+            self
+        }
+
+        /// Set the offset of the cosine.
+        pub fn cos_offset(mut self, cos_offset: f64) -> Self {
+            self.cos_offset = cos_offset;
+
+            self
+        }
+
+        /// Set the scale of the cosine.
+        pub fn cos_scale(mut self, cos_scale: f64) -> Self {
+            self.cos_scale = cos_scale;
+
+            self
+        }
+
+        /// Set the offset of the tangent.
+        pub fn tan_offset(mut self, tan_offset: f64) -> Self {
+            self.tan_offset = tan_offset;
+
+            self
+        }
+
+        /// Set the scale of the tangent.
+        pub fn tan_scale(mut self, tan_scale: f64) -> Self {
+            self.tan_scale = tan_scale;
+
+            self
+        }
+
+        /// Set the offset of the result.
+        pub fn offset(mut self, offset: f64) -> Self {
+            self.offset = offset;
+
+            self
+        }
+
+        /// Set the scale of the result.
+        pub fn scale(mut self, scale: f64) -> Self {
+            self.scale = scale;
+
+            self
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            *self != Self::default()
+        }
+    }
+
+    impl Default for Distortion {
+        fn default() -> Self {
+            Self {
+                sin_offset: 0.0,
+                sin_scale: 1.0,
+                cos_offset: 0.0,
+                cos_scale: 1.0,
+                tan_offset: 0.0,
+                tan_scale: 1.0,
+                offset: 0.0,
+                scale: 1.0,
+            }
+        }
+    }
+
+    /// Channel mix filter, which mixes the left and right channels into each
+    /// other.
+    ///
+    /// Each factor is between 0 and 1. The default keeps both channels
+    /// separate, and setting every factor to 0.5 makes the audio mono.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ChannelMix {
+        /// The factor of the left channel in the left channel.
+        pub left_to_left: f64,
+        /// The factor of the left channel in the right channel.
+        pub left_to_right: f64,
+        /// The factor of the right channel in the left channel.
+        pub right_to_left: f64,
+        /// The factor of the right channel in the right channel.
+        pub right_to_right: f64,
+    }
+
+    impl ChannelMix {
+        /// Create a new channel mix filter that keeps both channels separate,
+        /// to be configured with the other methods.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Set the factor of the left channel in the left channel.
+        pub fn left_to_left(mut self, left_to_left: f64) -> Self {
+            self.left_to_left = left_to_left;
+
+            self
+        }
+
+        /// Set the factor of the left channel in the right channel.
+        pub fn left_to_right(mut self, left_to_right: f64) -> Self {
+            self.left_to_right = left_to_right;
+
+            self
+        }
+
+        /// Set the factor of the right channel in the left channel.
+        pub fn right_to_left(mut self, right_to_left: f64) -> Self {
+            self.right_to_left = right_to_left;
+
+            self
+        }
+
+        /// Set the factor of the right channel in the right channel.
+        pub fn right_to_right(mut self, right_to_right: f64) -> Self {
+            self.right_to_right = right_to_right;
+
+            self
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            *self != Self::default()
+        }
+    }
+
+    impl Default for ChannelMix {
+        fn default() -> Self {
+            Self {
+                left_to_left: 1.0,
+                left_to_right: 0.0,
+                right_to_left: 0.0,
+                right_to_right: 1.0,
+            }
+        }
+    }
+
+    /// Low-pass filter, which suppresses high frequencies.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LowPass {
+        /// The smoothing factor. Values of 1 or less don't alter the audio.
+        pub smoothing: f64,
+    }
+
+    impl LowPass {
+        /// Create a new low-pass filter.
+        pub fn new(smoothing: f64) -> Self {
+            Self { smoothing }
+        }
+
+        /// Return whether the filter alters the audio.
+        pub fn enabled(&self) -> bool {
+            self.smoothing > 1.0
+        }
+    }
+
+    impl Default for LowPass {
+        fn default() -> Self {
+            Self::new(1.0)
+        }
+    }
+
     /// Update a player.
     #[skip_serializing_none]
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -482,6 +1048,8 @@ pub mod outgoing {
         pub volume: Option<i64>,
         /// The filters of the player.
         pub filters: Option<Filters>,
+        /// The ID of the mixer player to update, if any.
+        pub player_id: Option<String>,
     }
 
     impl Update {
@@ -500,8 +1068,16 @@ pub mod outgoing {
                 position: position.into(),
                 volume: volume.into(),
                 filters: filters.into(),
+                player_id: None,
             }
         }
+
+        /// Update a mixer player instead of the main player.
+        pub fn mixer_player(mut self, player_id: impl Into<String>) -> Self {
+            self.player_id = Some(player_id.into());
+
+            self
+        }
     }
 
     /// Destroy a player from a node.
@@ -512,6 +1088,9 @@ pub mod outgoing {
         pub op: Opcode,
         /// The guild ID of the player.
         pub guild_id: GuildId,
+        /// The ID of the mixer player to remove, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub player_id: Option<String>,
     }
 
     impl Destroy {
@@ -520,6 +1099,43 @@ pub mod outgoing {
             Self {
                 op: Opcode::Destroy,
                 guild_id,
+                player_id: None,
+            }
+        }
+
+        /// Remove a mixer player from the mixer instead of destroying the main
+        /// player.
+        pub fn mixer_player(mut self, player_id: impl Into<String>) -> Self {
+            self.player_id = Some(player_id.into());
+
+            self
+        }
+    }
+
+    /// Enable or disable the mixer of a player.
+    ///
+    /// While the mixer is enabled, the tracks of all mixer players are mixed
+    /// together. Mixer players are created by [playing] a track on them.
+    ///
+    /// [playing]: struct.Play.html#method.mixer_player
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Mixer {
+        /// The opcode of the event.
+        pub op: Opcode,
+        /// The guild ID of the player.
+        pub guild_id: GuildId,
+        /// Whether to enable the mixer.
+        pub enable: bool,
+    }
+
+    impl Mixer {
+        /// Create a new mixer event.
+        pub fn new(guild_id: GuildId, enable: bool) -> Self {
+            Self {
+                op: Opcode::Mixer,
+                guild_id,
+                enable,
             }
         }
     }
@@ -532,6 +1148,7 @@ pub mod incoming {
     use super::Opcode;
     use crate::http::Error;
     use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
     use twilight_model::id::GuildId;
 
     /// An incoming event from a Lavalink node.
@@ -629,17 +1246,33 @@ pub mod incoming {
         pub filters: Filters,
         /// Whether the player is destroyed.
         pub destroyed: Option<bool>,
-        /// Mixer, always None.
-        #[serde(skip)]
-        pub mixer: Option<()>,
-        /// Mixer enabled, always None.
-        #[serde(skip)]
-        pub mixer_enabled: Option<()>,
+        /// The state of each mixer player, keyed by its ID.
+        #[serde(default)]
+        pub mixer: HashMap<String, MixerPlayerState>,
+        /// Whether the mixer is enabled.
+        #[serde(default)]
+        pub mixer_enabled: bool,
         /// Frame loss and success, always None.
         #[serde(skip)]
         pub frame: Option<()>,
     }
 
+    /// The state of a mixer player.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MixerPlayerState {
+        /// The time of the mixer player.
+        pub time: i64,
+        /// The position of the mixer player.
+        pub position: Option<i64>,
+        /// Whether the mixer player is paused.
+        pub paused: bool,
+        /// Volume of the mixer player.
+        pub volume: i64,
+        /// Filters present.
+        pub filters: Filters,
+    }
+
     /// Statistics about a node and its host.
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -758,7 +1391,80 @@ pub mod incoming {
         /// The base64 track that was affected.
         pub track: String,
         /// The reason that the track ended.
-        pub reason: String,
+        pub reason: TrackEndReason,
+    }
+
+    /// The reason that a track ended.
+    #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    #[serde(from = "String", into = "String")]
+    pub enum TrackEndReason {
+        /// The track finished playing.
+        Finished,
+        /// The track failed to start, throwing an exception before providing
+        /// any audio.
+        LoadFailed,
+        /// The track was stopped.
+        Stopped,
+        /// The track was replaced by another track.
+        Replaced,
+        /// The track was stopped because the player was cleaned up.
+        Cleanup,
+        /// The track reached the end time that it was played with.
+        ///
+        /// This is never sent by the server. Instead, the client stops the
+        /// track and emits a track end event with this reason itself.
+        EndTimeReached,
+        /// A reason that isn't known by this crate.
+        Unknown(String),
+    }
+
+    impl TrackEndReason {
+        /// Whether the next track may be started, such as from a queue.
+        ///
+        /// This is the case if the track finished, reached its end time, or
+        /// failed to load, rather than being stopped or replaced.
+        pub fn may_start_next(&self) -> bool {
+            matches!(
+                self,
+                Self::Finished | Self::LoadFailed | Self::EndTimeReached
+            )
+        }
+
+        /// Retrieve the name of the reason as sent by the server.
+        pub fn as_str(&self) -> &str {
+            match self {
+                Self::Finished => "FINISHED",
+                Self::LoadFailed => "LOAD_FAILED",
+                Self::Stopped => "STOPPED",
+                Self::Replaced => "REPLACED",
+                Self::Cleanup => "CLEANUP",
+                Self::EndTimeReached => "END_TIME_REACHED",
+                Self::Unknown(reason) => reason,
+            }
+        }
+    }
+
+    impl From<String> for TrackEndReason {
+        fn from(reason: String) -> Self {
+            match reason.as_str() {
+                "FINISHED" => Self::Finished,
+                "LOAD_FAILED" => Self::LoadFailed,
+                "STOPPED" => Self::Stopped,
+                "REPLACED" => Self::Replaced,
+                "CLEANUP" => Self::Cleanup,
+                "END_TIME_REACHED" => Self::EndTimeReached,
+                _ => Self::Unknown(reason),
+            }
+        }
+    }
+
+    impl From<TrackEndReason> for String {
+        fn from(reason: TrackEndReason) -> Self {
+            match reason {
+                TrackEndReason::Unknown(reason) => reason,
+                other => other.as_str().to_owned(),
+            }
+        }
     }
 
     /// A track encountered exception.
@@ -820,11 +1526,124 @@ pub mod incoming {
         /// The reason for the close of websocket.
         pub reason: Option<String>,
         /// The code for this websocket close.
-        pub code: i64,
+        pub code: VoiceCloseCode,
         /// Whether it is closed by remote.
         pub by_remote: bool,
     }
 
+    /// The code that a Discord voice websocket was closed with.
+    #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+    #[serde(from = "i64", into = "i64")]
+    pub enum VoiceCloseCode {
+        /// An invalid opcode was sent.
+        UnknownOpcode,
+        /// An invalid payload was sent.
+        FailedToDecodePayload,
+        /// A payload was sent before identifying.
+        NotAuthenticated,
+        /// The token sent when identifying was incorrect.
+        AuthenticationFailed,
+        /// More than one identify payload was sent.
+        AlreadyAuthenticated,
+        /// The session is no longer valid.
+        SessionNoLongerValid,
+        /// The session timed out.
+        SessionTimeout,
+        /// The voice server couldn't be found.
+        ServerNotFound,
+        /// An unknown protocol was sent.
+        UnknownProtocol,
+        /// The bot was disconnected, such as by being kicked from the
+        /// channel or the channel being deleted.
+        Disconnected,
+        /// The voice server crashed.
+        VoiceServerCrashed,
+        /// An unknown encryption mode was sent.
+        UnknownEncryptionMode,
+        /// A code that isn't known by this crate, such as a standard
+        /// websocket close code.
+        Unknown(i64),
+    }
+
+    impl VoiceCloseCode {
+        /// Retrieve the numeric code.
+        pub fn code(self) -> i64 {
+            match self {
+                Self::UnknownOpcode => 4001,
+                Self::FailedToDecodePayload => 4002,
+                Self::NotAuthenticated => 4003,
+                Self::AuthenticationFailed => 4004,
+                Self::AlreadyAuthenticated => 4005,
+                Self::SessionNoLongerValid => 4006,
+                Self::SessionTimeout => 4009,
+                Self::ServerNotFound => 4011,
+                Self::UnknownProtocol => 4012,
+                Self::Disconnected => 4014,
+                Self::VoiceServerCrashed => 4015,
+                Self::UnknownEncryptionMode => 4016,
+                Self::Unknown(code) => code,
+            }
+        }
+
+        /// Whether the voice connection can be resumed with the same session
+        /// by forwarding the voice update again.
+        pub fn is_resumable(self) -> bool {
+            matches!(self, Self::VoiceServerCrashed)
+        }
+
+        /// Whether the voice connection can only be re-established with a new
+        /// session, which requires the bot to join the channel again.
+        pub fn requires_rejoin(self) -> bool {
+            matches!(
+                self,
+                Self::SessionNoLongerValid | Self::SessionTimeout | Self::ServerNotFound
+            )
+        }
+
+        /// Whether reconnecting is advisable.
+        ///
+        /// This is the case if the connection is [resumable], requires a
+        /// [rejoin], or was closed abnormally with a standard websocket close
+        /// code, such as after a network error. It isn't if the connection was
+        /// closed normally, if the bot was disconnected, or if the connection
+        /// failed due to an error that would occur again.
+        ///
+        /// [rejoin]: #method.requires_rejoin
+        /// [resumable]: #method.is_resumable
+        pub fn should_reconnect(self) -> bool {
+            match self {
+                Self::Unknown(code) => code < 4000 && code != 1000,
+                other => other.is_resumable() || other.requires_rejoin(),
+            }
+        }
+    }
+
+    impl From<i64> for VoiceCloseCode {
+        fn from(code: i64) -> Self {
+            match code {
+                4001 => Self::UnknownOpcode,
+                4002 => Self::FailedToDecodePayload,
+                4003 => Self::NotAuthenticated,
+                4004 => Self::AuthenticationFailed,
+                4005 => Self::AlreadyAuthenticated,
+                4006 => Self::SessionNoLongerValid,
+                4009 => Self::SessionTimeout,
+                4011 => Self::ServerNotFound,
+                4012 => Self::UnknownProtocol,
+                4014 => Self::Disconnected,
+                4015 => Self::VoiceServerCrashed,
+                4016 => Self::UnknownEncryptionMode,
+                other => Self::Unknown(other),
+            }
+        }
+    }
+
+    impl From<VoiceCloseCode> for i64 {
+        fn from(code: VoiceCloseCode) -> Self {
+            code.code()
+        }
+    }
+
     /// A player got destroyed.
     #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -846,12 +1665,157 @@ pub mod incoming {
 
 pub use self::{
     incoming::{
-        IncomingEvent, PlayerDestroy, PlayerUpdate, PlayerUpdateState, Stats, StatsCpu,
-        StatsFrames, StatsMemory, TrackEnd, TrackEventType, TrackException, TrackStart, TrackStuck,
-        WebsocketClose,
+        IncomingEvent, MixerPlayerState, PlayerDestroy, PlayerUpdate, PlayerUpdateState, Stats,
+        StatsCpu, StatsFrames, StatsMemory, TrackEnd, TrackEndReason, TrackEventType,
+        TrackException, TrackStart, TrackStuck, VoiceCloseCode, WebsocketClose,
     },
     outgoing::{
-        Destroy, Equalizer, Filters, GetPlayer, Karaoke, OutgoingEvent, Play,
-        SlimVoiceServerUpdate, Stop, Timescale, Tremolo, Update, Vibrato, VoiceUpdate,
+        ChannelMix, Destroy, Distortion, Equalizer, EqualizerBand, FilterKind, Filters, GetPlayer,
+        Karaoke, LowPass, Mixer, OutgoingEvent, Play, Rotation, SlimVoiceServerUpdate, Stop,
+        Timescale, Tremolo, Update, Vibrato, VoiceUpdate, Volume,
     },
 };
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FilterKind, Filters, Karaoke, Timescale, TrackEndReason, Tremolo, Vibrato, VoiceCloseCode,
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_track_end_reason() {
+        serde_test::assert_tokens(&TrackEndReason::Finished, &[Token::Str("FINISHED")]);
+        serde_test::assert_tokens(
+            &TrackEndReason::EndTimeReached,
+            &[Token::Str("END_TIME_REACHED")],
+        );
+        serde_test::assert_tokens(
+            &TrackEndReason::Unknown("SOMETHING_ELSE".to_owned()),
+            &[Token::Str("SOMETHING_ELSE")],
+        );
+    }
+
+    #[test]
+    fn test_voice_close_code() {
+        serde_test::assert_tokens(&VoiceCloseCode::Disconnected, &[Token::I64(4014)]);
+        serde_test::assert_tokens(&VoiceCloseCode::Unknown(1006), &[Token::I64(1006)]);
+        serde_test::assert_tokens(&VoiceCloseCode::Unknown(4020), &[Token::I64(4020)]);
+    }
+
+    #[test]
+    fn test_voice_close_code_should_reconnect() {
+        assert!(VoiceCloseCode::VoiceServerCrashed.should_reconnect());
+        assert!(VoiceCloseCode::SessionTimeout.should_reconnect());
+        assert!(VoiceCloseCode::Unknown(1006).should_reconnect());
+        assert!(!VoiceCloseCode::Unknown(1000).should_reconnect());
+        assert!(!VoiceCloseCode::Unknown(4020).should_reconnect());
+        assert!(!VoiceCloseCode::Disconnected.should_reconnect());
+        assert!(!VoiceCloseCode::AuthenticationFailed.should_reconnect());
+    }
+
+    #[test]
+    fn test_filters_unset_omitted() {
+        let filters = Filters::default().timescale(Timescale::new(1.2, 1.0, 1.0));
+
+        assert_eq!(
+            serde_json::to_value(&filters).unwrap(),
+            serde_json::json!({
+                "timescale": {
+                    "speed": 1.2,
+                    "pitch": 1.0,
+                    "rate": 1.0,
+                },
+            }),
+        );
+        assert_eq!(
+            serde_json::to_value(Filters::default()).unwrap(),
+            serde_json::json!({}),
+        );
+    }
+
+    #[test]
+    fn test_filters_reset() {
+        assert_eq!(
+            serde_json::to_value(Filters::reset()).unwrap(),
+            serde_json::json!({
+                "karaoke": {
+                    "level": 0.0,
+                    "monoLevel": 0.0,
+                    "filterBand": 220.0,
+                    "filterWidth": 100.0,
+                },
+                "timescale": {
+                    "speed": 1.0,
+                    "pitch": 1.0,
+                    "rate": 1.0,
+                },
+                "tremolo": {
+                    "frequency": 2.0,
+                    "depth": 0.0,
+                },
+                "vibrato": {
+                    "frequency": 2.0,
+                    "depth": 0.0,
+                },
+                "equalizer": {
+                    "bands": [],
+                },
+                "volume": {
+                    "volume": 1.0,
+                },
+                "rotation": {
+                    "rotationHz": 0.0,
+                },
+                "distortion": {
+                    "sinOffset": 0.0,
+                    "sinScale": 1.0,
+                    "cosOffset": 0.0,
+                    "cosScale": 1.0,
+                    "tanOffset": 0.0,
+                    "tanScale": 1.0,
+                    "offset": 0.0,
+                    "scale": 1.0,
+                },
+                "channelMix": {
+                    "leftToLeft": 1.0,
+                    "leftToRight": 0.0,
+                    "rightToLeft": 0.0,
+                    "rightToRight": 1.0,
+                },
+                "lowPass": {
+                    "smoothing": 1.0,
+                },
+            }),
+        );
+
+        let reset = Filters::reset();
+
+        assert!(FilterKind::ALL.iter().all(|kind| !reset.is_enabled(*kind)));
+    }
+
+    #[test]
+    fn test_filters_enabled_by_audio() {
+        assert!(!Karaoke::new(0.0, 0.0, 440.0, 50.0).enabled());
+        assert!(Karaoke::new(0.5, 0.0, 220.0, 100.0).enabled());
+        assert!(Karaoke::new(0.0, 0.5, 220.0, 100.0).enabled());
+        assert!(!Tremolo::new(4.0, 0.0).enabled());
+        assert!(Tremolo::new(2.0, 0.5).enabled());
+        assert!(!Vibrato::new(4.0, 0.0).enabled());
+        assert!(Vibrato::new(2.0, 0.5).enabled());
+
+        let filters: Filters = serde_json::from_value(serde_json::json!({
+            "karaoke": {
+                "enabled": true,
+                "level": 0.0,
+                "monoLevel": 0.0,
+                "filterBand": 440.0,
+                "filterWidth": 50.0,
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(filters.karaoke, Some(Karaoke::new(0.0, 0.0, 440.0, 50.0)));
+        assert!(!filters.is_enabled(FilterKind::Karaoke));
+    }
+}
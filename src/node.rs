@@ -18,39 +18,73 @@
 //! [`Lavalink`]: ../client/struct.Lavalink.html
 //! [`PlayerManager`]: ../player/struct.PlayerManager.html
 
+#[cfg(feature = "http")]
 use crate::{
-    model::{IncomingEvent, Opcode, OutgoingEvent, PlayerUpdate, Stats, StatsCpu, StatsMemory},
-    player::PlayerManager,
+    http::{LoadedTracks, Track},
+    model::PlayerUpdateState,
+};
+use crate::{
+    model::{
+        FilterKind, IncomingEvent, Opcode, OutgoingEvent, PlayerUpdate, Stats, StatsCpu,
+        StatsMemory, TrackEnd, TrackEndReason, TrackException, TrackStart, TrackStuck,
+    },
+    player::{CurrentTrack, Player, PlayerManager, PlayerStatus},
+    random,
+    selector::WeightedPenalty,
 };
 use async_tungstenite::{
     tokio::ConnectStream,
     tungstenite::{Error as TungsteniteError, Message},
     WebSocketStream,
 };
-use futures_channel::mpsc::{self, TrySendError, UnboundedReceiver, UnboundedSender};
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use futures_util::{
     future::{self, Either},
     lock::BiLock,
+    pin_mut,
     sink::SinkExt,
     stream::StreamExt,
 };
 use http::{
-    header::{ToStrError, AUTHORIZATION, CONNECTION, UPGRADE},
+    header::{HeaderValue, ToStrError},
     Error as HttpError, Request, Response, StatusCode,
 };
-use reqwest::{Client, Error as ReqwestError};
+use reqwest::Error as ReqwestError;
+#[cfg(feature = "http")]
+use reqwest::{Body, Client};
+#[cfg(feature = "http")]
+use serde::de::DeserializeOwned;
 use serde_json::Error as JsonError;
+#[cfg(feature = "http")]
+use std::convert::TryInto;
 use std::{
-    convert::TryInto,
+    collections::{HashSet, VecDeque},
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
+    mem,
     net::SocketAddr,
     num::ParseIntError,
-    sync::Arc,
-    time::Duration,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
 };
-use tokio::time::sleep;
-use twilight_model::id::UserId;
+use tokio::{
+    sync::watch::{self, Receiver as WatchReceiver, Sender as WatchSender},
+    time::sleep,
+};
+use twilight_model::id::{GuildId, UserId};
+use url::{ParseError, Url};
+
+/// The maximum duration of an HTTP request to a node, including connecting.
+#[cfg(feature = "http")]
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum duration of connecting to a node over HTTP.
+#[cfg(feature = "http")]
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// An error occurred while either initializing a connection or while running
 /// its event loop.
@@ -76,11 +110,31 @@ pub enum NodeError {
         /// The source of the error from `std`.
         source: ParseIntError,
     },
-    /// Connecting to the Lavalink server failed after several backoff attempts.
+    /// Connecting to the Lavalink server failed after the maximum number of
+    /// attempts allowed by the node's [`Reconnect`] policy.
+    ///
+    /// [`Reconnect`]: struct.Reconnect.html
     Connecting {
         /// The source of the error from the `tungstenite` crate.
         source: TungsteniteError,
     },
+    /// An event couldn't be buffered while the node was reconnecting, because
+    /// the buffer was full.
+    BufferFull {
+        /// The event that was dropped.
+        message: OutgoingEvent,
+    },
+    /// The node was closed, so events can no longer be sent to it.
+    Closed {
+        /// The event that couldn't be sent.
+        message: OutgoingEvent,
+    },
+    /// Sending a message to the Lavalink server failed, so the node will
+    /// reconnect and send the message again.
+    SendingMessage {
+        /// The source of the error from the `tungstenite` crate.
+        source: TungsteniteError,
+    },
     /// Serializing a JSON message to be sent to a Lavalink node failed.
     SerializingMessage {
         /// The message that couldn't be serialized.
@@ -91,7 +145,7 @@ pub enum NodeError {
     /// The given authorization for the node is incorrect.
     Unauthorized {
         /// The address of the node that failed to authorize.
-        address: SocketAddr,
+        address: NodeAddress,
         /// The authorization used to connect to the node.
         authorization: String,
     },
@@ -107,6 +161,9 @@ impl Display for NodeError {
             Self::ParsingResponseHeader { .. } => f.write_str("failed to parse response header"),
             Self::ParsingInt { .. } => f.write_str("failed to parse string to int"),
             Self::Connecting { .. } => f.write_str("failed to connect to the node"),
+            Self::BufferFull { .. } => f.write_str("outgoing event buffer is full"),
+            Self::Closed { .. } => f.write_str("the node was closed"),
+            Self::SendingMessage { .. } => f.write_str("failed to send message to the node"),
             Self::SerializingMessage { .. } => {
                 f.write_str("failed to serialize outgoing message as json")
             }
@@ -127,12 +184,268 @@ impl Error for NodeError {
             Self::ParsingResponseHeader { source } => Some(source),
             Self::ParsingInt { source } => Some(source),
             Self::Connecting { source } => Some(source),
+            Self::BufferFull { .. } => None,
+            Self::Closed { .. } => None,
+            Self::SendingMessage { source } => Some(source),
             Self::SerializingMessage { source, .. } => Some(source),
             Self::Unauthorized { .. } => None,
         }
     }
 }
 
+/// An error occurred while sending an HTTP request to a node, or while reading
+/// its response.
+#[derive(Debug)]
+pub enum RequestError {
+    /// Building the HTTP request failed.
+    BuildingRequest {
+        /// The source of the error from the `http` crate.
+        source: HttpError,
+    },
+    /// Deserializing the response body failed.
+    DeserializingResponse {
+        /// The response body that couldn't be deserialized.
+        body: Vec<u8>,
+        /// The source of the error from the `serde_json` crate.
+        source: JsonError,
+    },
+    /// Executing the HTTP request or reading its response failed, such as
+    /// because it timed out.
+    ExecutingRequest {
+        /// The source of the error from the `reqwest` crate.
+        source: ReqwestError,
+    },
+    /// The node responded with an unsuccessful status code.
+    UnexpectedStatus {
+        /// The status code of the response.
+        status: StatusCode,
+    },
+    /// The given authorization for the node is incorrect.
+    Unauthorized,
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::BuildingRequest { .. } => f.write_str("failed to build http request"),
+            Self::DeserializingResponse { .. } => f.write_str("failed to deserialize response"),
+            Self::ExecutingRequest { .. } => f.write_str("failed to execute http request"),
+            Self::UnexpectedStatus { status } => {
+                write!(f, "node responded with unexpected status {}", status)
+            }
+            Self::Unauthorized => f.write_str("the authorization used for the node is invalid"),
+        }
+    }
+}
+
+impl Error for RequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::BuildingRequest { source } => Some(source),
+            Self::DeserializingResponse { source, .. } => Some(source),
+            Self::ExecutingRequest { source } => Some(source),
+            Self::UnexpectedStatus { .. } => None,
+            Self::Unauthorized => None,
+        }
+    }
+}
+
+/// An error occurred while parsing a [`NodeAddress`].
+///
+/// [`NodeAddress`]: struct.NodeAddress.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NodeAddressError {
+    /// The URL has no host.
+    MissingHost,
+    /// Parsing the URL failed.
+    ParsingUrl {
+        /// The source of the error from the `url` crate.
+        source: ParseError,
+    },
+    /// The URL's scheme isn't one of `http`, `https`, `ws` or `wss`.
+    UnsupportedScheme {
+        /// The scheme of the URL.
+        scheme: String,
+    },
+}
+
+impl Display for NodeAddressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingHost => f.write_str("node url has no host"),
+            Self::ParsingUrl { .. } => f.write_str("failed to parse node url"),
+            Self::UnsupportedScheme { scheme } => {
+                write!(f, "node url scheme {} is not supported", scheme)
+            }
+        }
+    }
+}
+
+impl Error for NodeAddressError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::MissingHost => None,
+            Self::ParsingUrl { source } => Some(source),
+            Self::UnsupportedScheme { .. } => None,
+        }
+    }
+}
+
+/// The address of a Lavalink server, used for both its websocket and its HTTP
+/// endpoints.
+///
+/// An address can be parsed from a URL such as `wss://andesite.example.com/audio`,
+/// where the scheme determines whether TLS is used, and the path is the base
+/// path of all endpoints. Both `ws`/`http` and `wss`/`https` are accepted, and
+/// a URL without a scheme is treated as `http`.
+///
+/// Socket addresses can be converted into addresses without TLS.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_andesite::node::NodeAddress;
+///
+/// let address = "https://andesite.example.com/audio".parse::<NodeAddress>()?;
+///
+/// assert!(address.is_secure());
+/// assert_eq!(address.port(), 443);
+/// assert_eq!(address.websocket_url(), "wss://andesite.example.com:443/audio/");
+/// assert_eq!(
+///     address.http_url("/loadtracks"),
+///     "https://andesite.example.com:443/audio/loadtracks",
+/// );
+/// # Ok::<_, twilight_andesite::node::NodeAddressError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NodeAddress {
+    secure: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl NodeAddress {
+    /// Create a new address from a host and port, without a base path.
+    ///
+    /// Set `secure` to `true` to connect over TLS.
+    pub fn new(host: impl Into<String>, port: u16, secure: bool) -> Self {
+        Self {
+            secure,
+            host: host.into(),
+            port,
+            path: String::new(),
+        }
+    }
+
+    /// Parse an address from a URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeAddressError::ParsingUrl`] if the URL is invalid.
+    ///
+    /// Returns [`NodeAddressError::UnsupportedScheme`] if the scheme isn't
+    /// one of `http`, `https`, `ws` or `wss`.
+    ///
+    /// Returns [`NodeAddressError::MissingHost`] if the URL has no host.
+    ///
+    /// [`NodeAddressError::MissingHost`]: enum.NodeAddressError.html#variant.MissingHost
+    /// [`NodeAddressError::ParsingUrl`]: enum.NodeAddressError.html#variant.ParsingUrl
+    /// [`NodeAddressError::UnsupportedScheme`]: enum.NodeAddressError.html#variant.UnsupportedScheme
+    pub fn parse(url: impl AsRef<str>) -> Result<Self, NodeAddressError> {
+        let url = url.as_ref();
+        let url = if url.contains("://") {
+            Url::parse(url)
+        } else {
+            Url::parse(&format!("http://{}", url))
+        }
+        .map_err(|source| NodeAddressError::ParsingUrl { source })?;
+
+        let secure = match url.scheme() {
+            "http" | "ws" => false,
+            "https" | "wss" => true,
+            scheme => {
+                return Err(NodeAddressError::UnsupportedScheme {
+                    scheme: scheme.to_owned(),
+                })
+            }
+        };
+
+        let host = url.host_str().ok_or(NodeAddressError::MissingHost)?;
+        let port = url.port().unwrap_or(if secure { 443 } else { 80 });
+
+        Ok(Self {
+            secure,
+            host: host.to_owned(),
+            port,
+            path: url.path().trim_end_matches('/').to_owned(),
+        })
+    }
+
+    /// Return whether TLS is used to connect to the node.
+    pub fn is_secure(&self) -> bool {
+        self.secure
+    }
+
+    /// Return an immutable reference to the host of the node.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Return the port of the node.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Return an immutable reference to the base path of the node's
+    /// endpoints, which is empty or starts with a slash.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Return the URL of the node's websocket.
+    pub fn websocket_url(&self) -> String {
+        let scheme = if self.secure { "wss" } else { "ws" };
+
+        format!("{}://{}:{}{}/", scheme, self.host, self.port, self.path)
+    }
+
+    /// Return the URL of an HTTP endpoint of the node, such as `/loadtracks`.
+    pub fn http_url(&self, endpoint: &str) -> String {
+        let scheme = if self.secure { "https" } else { "http" };
+
+        format!(
+            "{}://{}:{}{}{}",
+            scheme, self.host, self.port, self.path, endpoint
+        )
+    }
+}
+
+impl Display for NodeAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.http_url(""))
+    }
+}
+
+impl From<SocketAddr> for NodeAddress {
+    fn from(address: SocketAddr) -> Self {
+        let host = match address {
+            SocketAddr::V4(address) => address.ip().to_string(),
+            SocketAddr::V6(address) => format!("[{}]", address.ip()),
+        };
+
+        Self::new(host, address.port(), false)
+    }
+}
+
+impl FromStr for NodeAddress {
+    type Err = NodeAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 /// The configuration that a [`Node`] uses to connect to a Lavalink server.
 ///
 /// [`Node`]: struct.Node.html
@@ -141,13 +454,47 @@ pub struct NodeConfig {
     /// The user ID of the bot.
     pub user_id: UserId,
     /// The address of the node.
-    pub address: SocketAddr,
+    pub address: NodeAddress,
     /// The password to use when authenticating.
     pub authorization: String,
     /// The details for resuming a Lavalink session, if any.
     ///
     /// Set this to `None` to disable resume capability.
     pub resume: Option<Resume>,
+    /// The policy for retrying when connecting to the node fails.
+    pub reconnect: Reconnect,
+    /// How long the node may be connecting or reconnecting before the
+    /// [`Lavalink`] client moves its players to other nodes, or `None` to only
+    /// move them once the node has disconnected permanently.
+    ///
+    /// The default [`Reconnect`] policy never gives up, so the node only
+    /// disconnects permanently if it is closed or its policy has a maximum
+    /// number of attempts. Defaults to 30 seconds.
+    ///
+    /// [`Lavalink`]: ../client/struct.Lavalink.html
+    /// [`Reconnect`]: struct.Reconnect.html
+    pub failover_after: Option<Duration>,
+    /// The buffer for events that are sent while the node is reconnecting.
+    pub buffer: OutgoingBuffer,
+    /// The regions that the node is tagged with, such as `"europe"`.
+    ///
+    /// Refer to the [`RegionAware`] selector for how these are used.
+    ///
+    /// [`RegionAware`]: ../selector/struct.RegionAware.html
+    pub regions: Vec<String>,
+    /// Whether the node supports Andesite's mixer, which players use to
+    /// crossfade between tracks.
+    ///
+    /// Set this to `false` for Lavalink nodes. Refer to [`Transition`] for
+    /// more information.
+    ///
+    /// [`Transition`]: ../player/struct.Transition.html
+    pub mixer: bool,
+    /// The kinds of filters that the node supports.
+    ///
+    /// Players refuse to send filters that alter the audio if the node doesn't
+    /// support them. Defaults to the filters that Andesite supports.
+    pub filters: HashSet<FilterKind>,
 }
 
 /// Configuration for a session which can be resumed.
@@ -157,12 +504,16 @@ pub struct Resume {
     /// session to be resumed for after a disconnect.
     pub timeout: u64,
     /// The connection id to resume as. Set to None to disable initial resume.
+    ///
+    /// The node keeps its own copy of this up to date with the id of its
+    /// current session, so that every reconnect resumes the latest session.
     pub connection_id: Option<u64>,
 }
 
 impl Resume {
-    /// Configure resume capability, providing the number of seconds that the
-    /// Lavalink server should queue events for when the connection is resumed.
+    /// Configure resume capability, providing the number of milliseconds that
+    /// the Lavalink server should queue events for when the connection is
+    /// resumed.
     pub fn new(timeout: u64) -> Self {
         Self::new_with_id(timeout, None)
     }
@@ -178,6 +529,125 @@ impl Resume {
     }
 }
 
+/// The policy for retrying when connecting or reconnecting to a node fails.
+///
+/// The delay between attempts starts at the base delay and doubles after every
+/// failed attempt, up to the maximum delay. By default, connecting is retried
+/// indefinitely with delays from 1 to 64 seconds and jitter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reconnect {
+    /// The maximum number of attempts before giving up. Set to `None` to never
+    /// give up.
+    pub max_attempts: Option<u32>,
+    /// The delay after the first failed attempt.
+    pub base_delay: Duration,
+    /// The maximum delay between attempts.
+    pub max_delay: Duration,
+    /// Whether to randomize each delay between half and all of its value, so
+    /// that many clients don't reconnect at the same time.
+    pub jitter: bool,
+}
+
+impl Reconnect {
+    /// Configure a reconnect policy that never gives up.
+    pub fn new(base_delay: Duration, max_delay: Duration, jitter: bool) -> Self {
+        Self::new_with_attempts(None, base_delay, max_delay, jitter)
+    }
+
+    /// Similar to [`new`], but allows you to specify the maximum number of
+    /// attempts.
+    ///
+    /// [`new`]: #method.new
+    pub fn new_with_attempts(
+        max_attempts: impl Into<Option<u32>>,
+        base_delay: Duration,
+        max_delay: Duration,
+        jitter: bool,
+    ) -> Self {
+        Self {
+            max_attempts: max_attempts.into(),
+            base_delay,
+            max_delay,
+            jitter,
+        }
+    }
+
+    /// Calculate the delay before the next attempt, given the number of
+    /// attempts that have failed so far.
+    ///
+    /// Returns `None` if the maximum number of attempts has been reached.
+    pub fn delay(&self, failed_attempts: u32) -> Option<Duration> {
+        if matches!(self.max_attempts, Some(max) if failed_attempts >= max) {
+            return None;
+        }
+
+        let exponent = failed_attempts.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .checked_mul(1 << exponent)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+
+        if !self.jitter {
+            return Some(delay);
+        }
+
+        let half = delay / 2;
+        let range = (delay - half).as_nanos() as u64;
+
+        Some(half + Duration::from_nanos(random::below(range.saturating_add(1))))
+    }
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1), Duration::from_secs(64), true)
+    }
+}
+
+/// What to do with an event that is sent while the outgoing buffer is full.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BufferOverflow {
+    /// Drop the oldest buffered event to make room for the new event.
+    DropOldest,
+    /// Drop the new event.
+    Reject,
+    /// Replace the oldest buffered event with the same guild and opcode as the
+    /// new event, which is superseded by it. The new event is dropped if there
+    /// is no such event.
+    Coalesce,
+}
+
+/// The buffer for events that are sent while a node is reconnecting.
+///
+/// Buffered events are sent in order once the node has reconnected. An event
+/// that is dropped instead of being buffered is returned from [`Node::send`]
+/// as a [`NodeError::BufferFull`], while buffered events that are dropped to
+/// make room for newer ones are logged. By default, up to 256 events are
+/// buffered and the oldest events are dropped.
+///
+/// [`Node::send`]: struct.Node.html#method.send
+/// [`NodeError::BufferFull`]: enum.NodeError.html#variant.BufferFull
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutgoingBuffer {
+    /// The maximum number of buffered events.
+    pub capacity: usize,
+    /// What to do with an event that is sent while the buffer is full.
+    pub overflow: BufferOverflow,
+}
+
+impl OutgoingBuffer {
+    /// Configure the outgoing buffer.
+    pub fn new(capacity: usize, overflow: BufferOverflow) -> Self {
+        Self { capacity, overflow }
+    }
+}
+
+impl Default for OutgoingBuffer {
+    fn default() -> Self {
+        Self::new(256, BufferOverflow::DropOldest)
+    }
+}
+
 impl NodeConfig {
     /// Create a new configuration for connecting to a node via
     /// [`Node::connect`].
@@ -189,7 +659,7 @@ impl NodeConfig {
     /// [`Node::connect`]: struct.Node.html#method.connect
     pub fn new(
         user_id: UserId,
-        address: impl Into<SocketAddr>,
+        address: impl Into<NodeAddress>,
         authorization: impl Into<String>,
         resume: impl Into<Option<Resume>>,
     ) -> Self {
@@ -198,17 +668,73 @@ impl NodeConfig {
             address: address.into(),
             authorization: authorization.into(),
             resume: resume.into(),
+            reconnect: Reconnect::default(),
+            failover_after: Some(Duration::from_secs(30)),
+            buffer: OutgoingBuffer::default(),
+            regions: Vec::new(),
+            mixer: true,
+            filters: FilterKind::ANDESITE.iter().copied().collect(),
         }
     }
 }
 
+/// The state of a node's connection to its Lavalink server.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NodeState {
+    /// The node is connecting for the first time.
+    ///
+    /// Nodes are returned by [`Node::connect`] in this state. Events sent in
+    /// the meantime are buffered until the node has connected.
+    ///
+    /// [`Node::connect`]: struct.Node.html#method.connect
+    Connecting,
+    /// The node is connected with a new session.
+    ///
+    /// After a reconnect, this means that the previous session couldn't be
+    /// resumed, so players on the node have been lost.
+    Connected,
+    /// The connection was lost, and the node is trying to reconnect according
+    /// to its [`Reconnect`] policy.
+    ///
+    /// [`Reconnect`]: struct.Reconnect.html
+    Reconnecting,
+    /// The node connected and resumed its previous session, whose id was
+    /// provided through [`Resume`] or by a previous connection.
+    ///
+    /// [`Resume`]: struct.Resume.html
+    Resumed,
+    /// The node has permanently stopped, either because reconnecting failed or
+    /// because the connection was shutdown. Events can no longer be sent.
+    Disconnected,
+}
+
+impl NodeState {
+    /// Return whether the node is connected and can send events, regardless
+    /// of whether its session was resumed.
+    pub fn is_connected(self) -> bool {
+        matches!(self, Self::Connected | Self::Resumed)
+    }
+}
+
+/// The events that are buffered while a node is connecting or reconnecting.
+#[derive(Debug, Default)]
+struct Buffered {
+    events: VecDeque<OutgoingEvent>,
+    reconnecting: bool,
+}
+
 #[derive(Debug)]
 struct NodeRef {
+    buffered: Arc<Mutex<Buffered>>,
     config: NodeConfig,
+    #[cfg(feature = "http")]
+    http: Client,
     lavalink_tx: UnboundedSender<OutgoingEvent>,
     players: PlayerManager,
     stats: BiLock<Stats>,
-    connection_id: u64,
+    state: WatchReceiver<NodeState>,
+    connection_id: Arc<AtomicU64>,
+    incoming_tx: UnboundedSender<IncomingEvent>,
 }
 
 /// A connection to a single Lavalink server. It receives events and forwards
@@ -224,11 +750,26 @@ impl Node {
     /// Connect to a node, providing a player manager so that the node can
     /// update player details.
     ///
+    /// The node is returned right away and connects in the background, while
+    /// its state is [`NodeState::Connecting`]. Events sent in the meantime are
+    /// buffered like while reconnecting. If connecting fails according to the
+    /// node's [`Reconnect`] policy, its state becomes
+    /// [`NodeState::Disconnected`].
+    ///
     /// Please refer to the [module] documentation for some additional
     /// information about directly creating and using nodes. You are encouraged
     /// to use the [`Lavalink`] client instead.
     ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::ExecutingRequest`] if the HTTP client of the node
+    /// couldn't be built.
+    ///
     /// [`Lavalink`]: ../client/struct.Lavalink.html
+    /// [`NodeError::ExecutingRequest`]: enum.NodeError.html#variant.ExecutingRequest
+    /// [`NodeState::Connecting`]: enum.NodeState.html#variant.Connecting
+    /// [`NodeState::Disconnected`]: enum.NodeState.html#variant.Disconnected
+    /// [`Reconnect`]: struct.Reconnect.html
     /// [module]: index.html
     pub async fn connect(
         config: NodeConfig,
@@ -253,52 +794,85 @@ impl Node {
             uptime: 0,
         });
 
-        let connection_id = {
-            let mut req = http::Request::get(format!("http://{}", config.address));
-            req = req.header(CONNECTION, "Upgrade");
-            req = req.header(UPGRADE, "WebSocket");
-            req = req.header(AUTHORIZATION, config.authorization.clone());
-            req = req.header("User-Id", config.user_id.to_string());
-
-            let req = req
-                .body("")
-                .map_err(|source| NodeError::BuildingConnectionRequest { source })?
-                .try_into()
-                .map_err(|source| NodeError::ExecutingRequest { source })?;
-            let res = Client::new()
-                .execute(req)
-                .await
-                .map_err(|source| NodeError::ExecutingRequest { source })?;
-
-            let header_id = res.headers().get("andesite-connection-id");
-            if let Some(id) = header_id {
-                let id = id
-                    .to_str()
-                    .map_err(|source| NodeError::ParsingResponseHeader { source })?
-                    .parse::<u64>()
-                    .map_err(|source| NodeError::ParsingInt { source })?;
-                id + 1
-            } else {
-                0
-            }
-        };
+        #[cfg(feature = "http")]
+        let http = Client::builder()
+            .connect_timeout(HTTP_CONNECT_TIMEOUT)
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .map_err(|source| NodeError::ExecutingRequest { source })?;
+
+        let (state_tx, state_rx) = watch::channel(NodeState::Connecting);
+        let (to_node, from_lavalink) = mpsc::unbounded();
+        let (to_lavalink, from_node) = mpsc::unbounded();
 
-        tracing::debug!("starting connection to {}", config.address);
-        let (conn_loop, lavalink_tx, lavalink_rx) =
-            Connection::connect(config.clone(), players.clone(), bilock_right).await?;
-        tracing::debug!("started connection to {}", config.address);
+        // Events are buffered until the first connection is made.
+        let buffered = Arc::new(Mutex::new(Buffered {
+            events: VecDeque::new(),
+            reconnecting: true,
+        }));
+
+        let connection_id = Arc::new(AtomicU64::new(0));
 
         let node = Self(Arc::new(NodeRef {
-            config,
-            lavalink_tx,
-            players,
+            buffered: Arc::clone(&buffered),
+            config: config.clone(),
+            #[cfg(feature = "http")]
+            http,
+            lavalink_tx: to_lavalink,
+            players: players.clone(),
             stats: bilock_left,
-            connection_id,
+            state: state_rx,
+            connection_id: Arc::clone(&connection_id),
+            incoming_tx: to_node.clone(),
         }));
 
-        tokio::spawn(conn_loop.run(node.clone()));
+        let task_node = node.clone();
+
+        tokio::spawn(async move {
+            let mut node_from = from_node;
+
+            tracing::debug!("starting connection to {}", config.address);
 
-        Ok((node, lavalink_rx))
+            let session = match connect_buffered(&config, &buffered, &mut node_from).await {
+                Ok(Some(session)) => session,
+                Ok(None) => {
+                    tracing::debug!("node {} closed before connecting", config.address);
+                    let _ = state_tx.send(NodeState::Disconnected);
+
+                    return;
+                }
+                Err(source) => {
+                    tracing::warn!("couldn't connect to node {}: {}", config.address, source);
+                    let _ = state_tx.send(NodeState::Disconnected);
+
+                    return;
+                }
+            };
+
+            tracing::debug!("started connection to {}", config.address);
+
+            let mut connection = Connection {
+                buffered,
+                config,
+                connection: session.stream,
+                connection_id,
+                node_from,
+                node_to: to_node,
+                players,
+                state: state_tx,
+                stats: bilock_right,
+            };
+            connection.set_connection_id(session.connection_id);
+            connection.set_state(if session.resumed {
+                NodeState::Resumed
+            } else {
+                NodeState::Connected
+            });
+
+            connection.run(task_node).await;
+        });
+
+        Ok((node, from_lavalink))
     }
 
     /// Retrieve an immutable reference to the node's configuration.
@@ -311,124 +885,371 @@ impl Node {
         &self.0.players
     }
 
-    /// Retrieve an immutable reference to the node's configuration.
+    /// Send an event to the Lavalink server.
     ///
-    /// Note that sending player events through the node's sender won't update
-    /// player states, such as whether it's paused.
-    pub fn send(&self, event: impl Into<OutgoingEvent>) -> Result<(), TrySendError<OutgoingEvent>> {
-        self.sender().unbounded_send(event.into())
+    /// Events sent while the node is reconnecting are held in the node's
+    /// [`OutgoingBuffer`] and sent once it has reconnected.
+    ///
+    /// Note that sending player events through the node won't update player
+    /// states until the node reports them. Use [`Player::send`] to update them
+    /// right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::BufferFull`] if the node is reconnecting and its
+    /// buffer is full, and its overflow policy drops the event.
+    ///
+    /// Returns [`NodeError::Closed`] if the node was closed.
+    ///
+    /// [`NodeError::BufferFull`]: enum.NodeError.html#variant.BufferFull
+    /// [`NodeError::Closed`]: enum.NodeError.html#variant.Closed
+    /// [`OutgoingBuffer`]: struct.OutgoingBuffer.html
+    /// [`Player::send`]: ../player/struct.Player.html#method.send
+    pub fn send(&self, event: impl Into<OutgoingEvent>) -> Result<(), NodeError> {
+        let event = event.into();
+
+        if self.0.lavalink_tx.is_closed() {
+            return Err(NodeError::Closed { message: event });
+        }
+
+        let mut buffered = lock_buffered(&self.0.buffered);
+
+        if buffered.reconnecting {
+            return push_buffered(&mut buffered.events, &self.0.config.buffer, event);
+        }
+
+        self.0
+            .lavalink_tx
+            .unbounded_send(event)
+            .map_err(|source| NodeError::Closed {
+                message: source.into_inner(),
+            })
     }
 
     /// Retrieve a unique sender to send events to the Lavalink server.
     ///
     /// Note that sending player events through the node's sender won't update
-    /// player states, such as whether it's paused.
+    /// player states until the node reports them. Use [`Player::send`] to
+    /// update them right away.
+    ///
+    /// Unlike [`send`], events that are dropped because the node's buffer is
+    /// full are only logged.
+    ///
+    /// [`Player::send`]: ../player/struct.Player.html#method.send
+    /// [`send`]: #method.send
     pub fn sender(&self) -> UnboundedSender<OutgoingEvent> {
         self.0.lavalink_tx.clone()
     }
 
+    /// Handle the end of a track that the Lavalink server didn't report, such
+    /// as when it reached its end time, as if the server reported it.
+    pub(crate) fn end_track(&self, end: TrackEnd) {
+        handle_track_end(&self.0.players, &self.0.config.address, &end);
+
+        // It's fine if the rx end dropped, often users don't need to care about
+        // these events.
+        let _ = self
+            .0
+            .incoming_tx
+            .unbounded_send(IncomingEvent::TrackEnd(end));
+    }
+
+    /// Close the connection to the Lavalink server.
+    ///
+    /// Events that were sent before are still forwarded, and then the
+    /// websocket is closed and the node's state becomes
+    /// [`NodeState::Disconnected`]. Events can't be sent to the node
+    /// afterwards.
+    ///
+    /// If the node is connecting or reconnecting, connecting is canceled and
+    /// the events that were buffered in the meantime are dropped.
+    ///
+    /// [`NodeState::Disconnected`]: enum.NodeState.html#variant.Disconnected
+    pub fn close(&self) {
+        self.0.lavalink_tx.close_channel();
+    }
+
     /// Retrieve a copy of the node's stats.
     pub async fn stats(&self) -> Stats {
         (*self.0.stats.lock().await).clone()
     }
 
-    /// Retrieve the connection id of the node.
-    pub fn connection_id(&self) -> u64 {
-        self.0.connection_id
+    /// Retrieve the current state of the node's connection.
+    pub fn state(&self) -> NodeState {
+        *self.0.state.borrow()
     }
 
-    /// Retrieve the calculated penalty score of the node.
+    /// Retrieve a receiver that is notified whenever the state of the node's
+    /// connection changes.
     ///
-    /// This score can be used to calculate how loaded the server is. A higher
-    /// number means it is more heavily loaded.
-    pub async fn penalty(&self) -> i32 {
-        let stats = self.0.stats.lock().await;
-        let cpu = 1.05f64.powf(100f64 * stats.cpu.system_load) * 10f64 - 10f64;
-
-        let (deficit_frame, null_frame) = (
-            1.03f64
-                .powf(500f64 * (stats.frames.as_ref().map_or(0, |f| f.deficit) as f64 / 3000f64))
-                * 300f64
-                - 300f64,
-            (1.03f64
-                .powf(500f64 * (stats.frames.as_ref().map_or(0, |f| f.nulled) as f64 / 3000f64))
-                * 300f64
-                - 300f64)
-                * 2f64,
-        );
+    /// # Examples
+    ///
+    /// Log every state change of a node:
+    ///
+    /// ```no_run
+    /// # use twilight_andesite::Node;
+    /// # async fn example(node: Node) {
+    /// let mut states = node.state_changes();
+    ///
+    /// while states.changed().await.is_ok() {
+    ///     println!("node is now {:?}", *states.borrow());
+    /// }
+    /// # }
+    /// ```
+    pub fn state_changes(&self) -> WatchReceiver<NodeState> {
+        self.0.state.clone()
+    }
 
-        stats.playing_players as i32 + cpu as i32 + deficit_frame as i32 + null_frame as i32
+    /// Retrieve the connection id of the node's current session.
+    ///
+    /// This is updated every time the node connects. Provide it to
+    /// [`Resume::new_with_id`] to resume the session after restarting, for as
+    /// long as the session's [resume timeout] allows. Returns 0 if the server
+    /// didn't provide a connection id.
+    ///
+    /// [`Resume::new_with_id`]: struct.Resume.html#method.new_with_id
+    /// [resume timeout]: struct.Resume.html#structfield.timeout
+    pub fn connection_id(&self) -> u64 {
+        self.0.connection_id.load(Ordering::Relaxed)
     }
 
-    /// Provide a player update event.
-    pub fn provide_player_update(
+    /// Load a list of tracks that match an identifier, such as a URL or a
+    /// search query like `ytsearch:never gonna give you up`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequestError`] if the request failed, timed out, or its
+    /// response couldn't be deserialized.
+    ///
+    /// [`RequestError`]: enum.RequestError.html
+    #[cfg(feature = "http")]
+    pub async fn load_tracks(
         &self,
-        players: &PlayerManager,
-        update: &PlayerUpdate,
-    ) -> Result<(), NodeError> {
-        if let Some(destroyed) = update.state.destroyed {
-            if destroyed {
-                return Ok(());
-            }
-        }
+        identifier: impl AsRef<str>,
+    ) -> Result<LoadedTracks, RequestError> {
+        let req = crate::http::load_track(self.0.config.clone(), identifier)
+            .map_err(|source| RequestError::BuildingRequest { source })?;
+
+        self.request(req).await
+    }
+
+    /// Decode a base64 track string into its track information.
+    ///
+    /// To decode tracks without a request, use the [`track`] module.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequestError`] if the request failed, timed out, or its
+    /// response couldn't be deserialized.
+    ///
+    /// [`RequestError`]: enum.RequestError.html
+    /// [`track`]: ../track/index.html
+    #[cfg(feature = "http")]
+    pub async fn decode_track(&self, track: impl AsRef<str>) -> Result<Track, RequestError> {
+        let req = crate::http::decode_track(self.0.config.clone(), track)
+            .map_err(|source| RequestError::BuildingRequest { source })?;
+
+        self.request(req).await
+    }
+
+    /// Decode multiple base64 track strings in a single request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequestError`] if the request failed, timed out, or its
+    /// response couldn't be deserialized.
+    ///
+    /// [`RequestError`]: enum.RequestError.html
+    #[cfg(feature = "http")]
+    pub async fn decode_tracks(
+        &self,
+        tracks: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<Track>, RequestError> {
+        let req = crate::http::decode_tracks(self.0.config.clone(), tracks)
+            .map_err(|source| RequestError::BuildingRequest { source })?;
+
+        self.request(req).await
+    }
+
+    /// Retrieve the state of a guild's player from the node.
+    ///
+    /// Returns `None` if the node has no player for the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RequestError`] if the request failed, timed out, or its
+    /// response couldn't be deserialized.
+    ///
+    /// [`RequestError`]: enum.RequestError.html
+    #[cfg(feature = "http")]
+    pub async fn get_player(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Option<PlayerUpdateState>, RequestError> {
+        let req = crate::http::get_player(self.0.config.clone(), guild_id)
+            .map_err(|source| RequestError::BuildingRequest { source })?;
+
+        match self.request(req).await {
+            Ok(state) => Ok(state),
+            Err(RequestError::UnexpectedStatus {
+                status: StatusCode::NOT_FOUND,
+            }) => Ok(None),
+            Err(source) => Err(source),
+        }
+    }
+
+    #[cfg(feature = "http")]
+    async fn request<T: DeserializeOwned>(
+        &self,
+        req: Request<impl Into<Body>>,
+    ) -> Result<T, RequestError> {
+        let req = req
+            .try_into()
+            .map_err(|source| RequestError::ExecutingRequest { source })?;
+        let res = self
+            .0
+            .http
+            .execute(req)
+            .await
+            .map_err(|source| RequestError::ExecutingRequest { source })?;
+
+        match res.status() {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(RequestError::Unauthorized)
+            }
+            status if !status.is_success() => {
+                return Err(RequestError::UnexpectedStatus { status })
+            }
+            _ => {}
+        }
+
+        let body = res
+            .bytes()
+            .await
+            .map_err(|source| RequestError::ExecutingRequest { source })?;
+
+        serde_json::from_slice(&body).map_err(|source| RequestError::DeserializingResponse {
+            body: body.to_vec(),
+            source,
+        })
+    }
+
+    /// Retrieve the calculated penalty score of the node.
+    ///
+    /// This score can be used to calculate how loaded the server is. A higher
+    /// number means it is more heavily loaded.
+    pub async fn penalty(&self) -> i32 {
+        let stats = self.0.stats.lock().await;
+
+        WeightedPenalty::default().penalty(&stats) as i32
+    }
+
+    /// Provide a player update event.
+    pub fn provide_player_update(
+        &self,
+        players: &PlayerManager,
+        update: &PlayerUpdate,
+    ) -> Result<(), NodeError> {
+        if let Some(destroyed) = update.state.destroyed {
+            if destroyed {
+                return Ok(());
+            }
+        }
 
         let mut player = match players.get_mut(&update.guild_id) {
             Some(player) => player,
             None => players.get_or_insert(update.guild_id, self.clone()),
         };
 
+        // The update is stale if the player has moved to another node.
+        if player.node().config().address != self.0.config.address {
+            return Ok(());
+        }
+
         *player.value_mut().time_mut() = update.state.time;
+        *player.value_mut().updated_at_mut() = Some(Instant::now());
         *player.value_mut().position_mut() = update.state.position;
         *player.value_mut().paused_mut() = update.state.paused;
+
+        let status = player.value_mut().status_mut();
+
+        match (*status, update.state.paused) {
+            (PlayerStatus::Playing, true) => *status = PlayerStatus::Paused,
+            (PlayerStatus::Paused, false) => *status = PlayerStatus::Playing,
+            _ => {}
+        }
+
         *player.value_mut().volume_mut() = update.state.volume;
         *player.value_mut().filters_mut() = update.state.filters.clone();
+        *player.value_mut().mixer_enabled_mut() = update.state.mixer_enabled;
+        *player.value_mut().mixer_players_mut() = update.state.mixer.clone();
+        player.value_mut().reconcile();
 
         Ok(())
     }
 }
 
 struct Connection {
+    buffered: Arc<Mutex<Buffered>>,
     config: NodeConfig,
     connection: WebSocketStream<ConnectStream>,
+    connection_id: Arc<AtomicU64>,
     node_from: UnboundedReceiver<OutgoingEvent>,
     node_to: UnboundedSender<IncomingEvent>,
     players: PlayerManager,
+    state: WatchSender<NodeState>,
     stats: BiLock<Stats>,
 }
 
 impl Connection {
-    async fn connect(
-        config: NodeConfig,
-        players: PlayerManager,
-        stats: BiLock<Stats>,
-    ) -> Result<
-        (
-            Self,
-            UnboundedSender<OutgoingEvent>,
-            UnboundedReceiver<IncomingEvent>,
-        ),
-        NodeError,
-    > {
-        let connection = reconnect(&config).await?;
+    /// Record the id of a new session, so that the next reconnect resumes it.
+    fn set_connection_id(&mut self, connection_id: Option<u64>) {
+        let connection_id = match connection_id {
+            Some(connection_id) => connection_id,
+            None => return,
+        };
 
-        let (to_node, from_lavalink) = mpsc::unbounded();
-        let (to_lavalink, from_node) = mpsc::unbounded();
+        tracing::debug!(
+            "node {} has connection id {}",
+            self.config.address,
+            connection_id
+        );
+        self.connection_id.store(connection_id, Ordering::Relaxed);
 
-        Ok((
-            Self {
-                config,
-                connection,
-                node_from: from_node,
-                node_to: to_node,
-                players,
-                stats,
-            },
-            to_lavalink,
-            from_lavalink,
-        ))
+        if let Some(resume) = self.config.resume.as_mut() {
+            resume.connection_id = Some(connection_id);
+        }
     }
 
-    async fn run(mut self, node: Node) -> Result<(), NodeError> {
+    async fn run(mut self, node: Node) {
+        if let Err(source) = self.run_loop(node).await {
+            tracing::warn!(
+                "connection to node {} ended permanently: {}",
+                self.config.address,
+                source
+            );
+        }
+
+        self.set_state(NodeState::Disconnected);
+    }
+
+    fn set_state(&self, state: NodeState) {
+        tracing::debug!("node {} is now {:?}", self.config.address, state);
+
+        // The node holds a receiver for as long as it exists.
+        let _ = self.state.send(state);
+    }
+
+    async fn run_loop(&mut self, node: Node) -> Result<(), NodeError> {
+        // Send the events that were buffered while connecting.
+        if let Err(source) = self.flush().await {
+            tracing::warn!("{} {}, reconnecting", source, self.config.address);
+
+            if !self.reconnect_buffered().await? {
+                return Ok(());
+            }
+        }
+
         loop {
             let from_lavalink = self.connection.next();
             let to_lavalink = self.node_from.next();
@@ -439,27 +1260,23 @@ impl Connection {
                 }
                 Either::Left((_, _)) => {
                     tracing::debug!("connection to {} closed, reconnecting", self.config.address);
-                    self.connection = reconnect(&self.config).await?;
+
+                    if !self.reconnect_buffered().await? {
+                        break;
+                    }
                 }
                 Either::Right((Some(outgoing), _)) => {
-                    tracing::debug!(
-                        "forwarding event to {}: {:?}",
-                        self.config.address,
-                        outgoing
-                    );
-
-                    let payload = serde_json::to_string(&outgoing).map_err(|source| {
-                        NodeError::SerializingMessage {
-                            message: outgoing,
-                            source,
-                        }
-                    })?;
+                    if let Err(source) = self.send(outgoing).await {
+                        tracing::warn!("{} {}, reconnecting", source, self.config.address);
 
-                    let msg = Message::Text(payload);
-                    self.connection.send(msg).await.unwrap();
+                        if !self.reconnect_buffered().await? {
+                            break;
+                        }
+                    }
                 }
                 Either::Right((_, _)) => {
                     tracing::debug!("node {} closed, ending connection", self.config.address);
+                    let _ = self.connection.close(None).await;
 
                     break;
                 }
@@ -469,7 +1286,121 @@ impl Connection {
         Ok(())
     }
 
-    async fn incoming(&mut self, incoming: Message, node: Node) -> Result<bool, NodeError> {
+    /// Reconnect to the node, buffering events sent in the meantime and then
+    /// sending them in order.
+    ///
+    /// Returns `false` if reconnecting was canceled because the node was
+    /// closed.
+    async fn reconnect_buffered(&mut self) -> Result<bool, NodeError> {
+        loop {
+            {
+                let mut buffered = lock_buffered(&self.buffered);
+                buffered.reconnecting = true;
+
+                // Buffer the events that are already on their way first, so
+                // that they're sent before events that are sent from now on.
+                while let Ok(outgoing) = self.node_from.try_recv() {
+                    if let Err(source) =
+                        push_buffered(&mut buffered.events, &self.config.buffer, outgoing)
+                    {
+                        tracing::warn!("{:?}", source);
+                    }
+                }
+            }
+
+            self.set_state(NodeState::Reconnecting);
+
+            let session =
+                match connect_buffered(&self.config, &self.buffered, &mut self.node_from).await? {
+                    Some(session) => session,
+                    None => return Ok(false),
+                };
+
+            self.connection = session.stream;
+            self.set_connection_id(session.connection_id);
+
+            if session.resumed {
+                self.set_state(NodeState::Resumed);
+            } else {
+                self.set_state(NodeState::Connected);
+            }
+
+            match self.flush().await {
+                Ok(()) => return Ok(true),
+                Err(source) => {
+                    tracing::warn!("{} {}, reconnecting", source, self.config.address);
+                }
+            }
+        }
+    }
+
+    /// Send all buffered events in order.
+    ///
+    /// Events that are sent from now on are no longer buffered, and are sent
+    /// after the buffered events. If sending fails, the events that weren't
+    /// sent are buffered again.
+    async fn flush(&mut self) -> Result<(), NodeError> {
+        let mut events = {
+            let mut buffered = lock_buffered(&self.buffered);
+            buffered.reconnecting = false;
+
+            mem::take(&mut buffered.events)
+        };
+
+        if !events.is_empty() {
+            tracing::debug!(
+                "sending {} buffered events to {}",
+                events.len(),
+                self.config.address
+            );
+        }
+
+        while let Some(outgoing) = events.pop_front() {
+            if let Err(source) = self.send(outgoing).await {
+                lock_buffered(&self.buffered).events.extend(events);
+
+                return Err(source);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send an event to the node.
+    ///
+    /// If sending fails, the event is put at the front of the buffer so that
+    /// it is sent first after reconnecting.
+    async fn send(&mut self, outgoing: OutgoingEvent) -> Result<(), NodeError> {
+        tracing::debug!(
+            "forwarding event to {}: {:?}",
+            self.config.address,
+            outgoing
+        );
+
+        let payload = match serde_json::to_string(&outgoing) {
+            Ok(payload) => payload,
+            Err(source) => {
+                let source = NodeError::SerializingMessage {
+                    message: outgoing,
+                    source,
+                };
+                tracing::warn!("dropping event: {}", source);
+
+                return Ok(());
+            }
+        };
+
+        if let Err(source) = self.connection.send(Message::Text(payload)).await {
+            lock_buffered(&self.buffered).events.push_front(outgoing);
+
+            return Err(NodeError::SendingMessage { source });
+        }
+
+        Ok(())
+    }
+
+    /// Handle a message from the node.
+    async fn incoming(&mut self, incoming: Message, node: Node) -> Result<(), NodeError> {
         tracing::debug!(
             "received message from {}: {:?}",
             self.config.address,
@@ -478,10 +1409,12 @@ impl Connection {
 
         let text = match incoming {
             Message::Close(_) => {
+                // The connection ends once the close is answered, after which
+                // the node reconnects.
                 tracing::debug!("got close, closing connection");
                 let _ = self.connection.send(Message::Close(None)).await;
 
-                return Ok(false);
+                return Ok(());
             }
             Message::Ping(data) => {
                 tracing::debug!("got ping, sending pong");
@@ -490,13 +1423,13 @@ impl Connection {
                 // We don't need to immediately care if a pong fails.
                 let _ = self.connection.send(msg).await;
 
-                return Ok(true);
+                return Ok(());
             }
             Message::Text(text) => text,
             other => {
                 tracing::debug!("got pong or bytes payload: {:?}", other);
 
-                return Ok(true);
+                return Ok(());
             }
         };
 
@@ -505,10 +1438,41 @@ impl Connection {
             Err(_) => {
                 tracing::warn!("unknown message from lavalink node: {}", text);
 
-                return Ok(true);
+                return Ok(());
             }
         };
 
+        match event {
+            IncomingEvent::PlayerUpdate(_)
+            | IncomingEvent::PlayerDestroy(_)
+            | IncomingEvent::TrackStart(_)
+            | IncomingEvent::TrackEnd(_)
+            | IncomingEvent::TrackException(_)
+            | IncomingEvent::TrackStuck(_)
+                if !self.owns(event.guild_id()) =>
+            {
+                tracing::debug!(
+                    "dropping event from {} for guild {} on another node: {:?}",
+                    self.config.address,
+                    event.guild_id(),
+                    event
+                );
+
+                return Ok(());
+            }
+            IncomingEvent::TrackEnd(ref end) if self.ended_at_end_time(end) => {
+                tracing::debug!(
+                    "dropping end from {} of track in guild {} that reached its end time: {:?}",
+                    self.config.address,
+                    end.guild_id,
+                    end
+                );
+
+                return Ok(());
+            }
+            _ => {}
+        }
+
         match event {
             IncomingEvent::PlayerUpdate(ref update) => {
                 self.player_update(update, node.clone()).await?;
@@ -519,6 +1483,18 @@ impl Connection {
             IncomingEvent::Stats(ref stats) => {
                 self.stats(stats).await?;
             }
+            IncomingEvent::TrackStart(ref start) => {
+                self.track_start(start);
+            }
+            IncomingEvent::TrackEnd(ref end) => {
+                self.track_end(end);
+            }
+            IncomingEvent::TrackException(ref exception) => {
+                self.track_exception(exception);
+            }
+            IncomingEvent::TrackStuck(ref stuck) => {
+                self.track_stuck(stuck);
+            }
             _ => {}
         }
 
@@ -528,7 +1504,23 @@ impl Connection {
             let _ = self.node_to.unbounded_send(event);
         }
 
-        Ok(true)
+        Ok(())
+    }
+
+    /// Whether the player of a guild, if there is one, is linked to this node
+    /// rather than having moved to another one.
+    fn owns(&self, guild_id: GuildId) -> bool {
+        self.players
+            .get(&guild_id)
+            .is_none_or(|player| player.node().config().address == self.config.address)
+    }
+
+    /// Whether a track that the node reports the end of was already ended by
+    /// its player at its end time.
+    fn ended_at_end_time(&self, end: &TrackEnd) -> bool {
+        self.players
+            .get(&end.guild_id)
+            .is_some_and(|player| player.ended_at_end_time(end))
     }
 
     async fn player_update(&self, update: &PlayerUpdate, node: Node) -> Result<(), NodeError> {
@@ -540,10 +1532,145 @@ impl Connection {
 
         Ok(())
     }
+
+    fn track_start(&self, start: &TrackStart) {
+        if let Some(mut player) = self.players.get_mut(&start.guild_id) {
+            player.forget_ended();
+            *player.current_track_mut() = Some(CurrentTrack::new(start.track.as_str()));
+            *player.status_mut() = if *player.paused_mut() {
+                PlayerStatus::Paused
+            } else {
+                PlayerStatus::Playing
+            };
+            player.confirm_track();
+        }
+    }
+
+    fn track_end(&self, end: &TrackEnd) {
+        handle_track_end(&self.players, &self.config.address, end);
+    }
+
+    fn track_exception(&self, exception: &TrackException) {
+        if let Some(mut player) = self.players.get_mut(&exception.guild_id) {
+            if is_current(&mut player, &exception.track) {
+                *player.status_mut() = PlayerStatus::Errored;
+            }
+
+            player.fail_track(&exception.track);
+        }
+    }
+
+    fn track_stuck(&self, stuck: &TrackStuck) {
+        if let Some(mut player) = self.players.get_mut(&stuck.guild_id) {
+            if is_current(&mut player, &stuck.track) {
+                *player.status_mut() = PlayerStatus::Stuck;
+            }
+        }
+    }
+}
+
+/// Update a player after its track ended, and play the next track of its
+/// queue if the track didn't end early.
+///
+/// The end is ignored if the player has moved away from the node at the given
+/// address in the meantime.
+fn handle_track_end(players: &PlayerManager, address: &NodeAddress, end: &TrackEnd) {
+    if let Some(mut player) = players.get_mut(&end.guild_id) {
+        if player.node().config().address != *address {
+            return;
+        }
+
+        // The queue may have advanced before the track ended, to transition
+        // into the next track.
+        let advanced = player.advanced_past(&end.track);
+
+        if !is_current(&mut player, &end.track) {
+            return;
+        }
+
+        *player.current_track_mut() = None;
+
+        // Keep the reason that the track stopped playing early, if any.
+        let status = player.status_mut();
+
+        if !matches!(status, PlayerStatus::Stuck | PlayerStatus::Errored) {
+            *status = PlayerStatus::Idle;
+        }
+
+        player.confirm_track();
+
+        if advanced || !end.reason.may_start_next() {
+            return;
+        }
+
+        let failed = end.reason == TrackEndReason::LoadFailed;
+
+        if let Err(source) = player.advance_after(&end.track, failed) {
+            tracing::warn!(
+                "couldn't play next track in guild {}: {}",
+                end.guild_id,
+                source
+            );
+        }
+    }
+}
+
+/// Whether a track is the one that a player is confirmed to be playing.
+fn is_current(player: &mut Player, track: &str) -> bool {
+    player
+        .current_track_mut()
+        .as_ref()
+        .is_some_and(|current| current.track == track)
+}
+
+/// Lock the buffered events of a node.
+fn lock_buffered(buffered: &Mutex<Buffered>) -> MutexGuard<'_, Buffered> {
+    buffered.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Add an event to the outgoing buffer, applying the overflow policy if the
+/// buffer is full.
+///
+/// Returns [`NodeError::BufferFull`] if the event was dropped. Buffered events
+/// that are dropped to make room for it are only logged.
+///
+/// [`NodeError::BufferFull`]: enum.NodeError.html#variant.BufferFull
+fn push_buffered(
+    buffer: &mut VecDeque<OutgoingEvent>,
+    config: &OutgoingBuffer,
+    outgoing: OutgoingEvent,
+) -> Result<(), NodeError> {
+    if buffer.len() < config.capacity {
+        buffer.push_back(outgoing);
+
+        return Ok(());
+    }
+
+    let dropped = match config.overflow {
+        BufferOverflow::DropOldest => buffer.pop_front(),
+        BufferOverflow::Reject => None,
+        BufferOverflow::Coalesce => {
+            let superseded = buffer.iter().position(|event| {
+                event.guild_id() == outgoing.guild_id() && event.op() == outgoing.op()
+            });
+
+            superseded.and_then(|index| buffer.remove(index))
+        }
+    };
+
+    match dropped {
+        Some(dropped) => {
+            buffer.push_back(outgoing);
+            tracing::warn!("{:?}", NodeError::BufferFull { message: dropped });
+
+            Ok(())
+        }
+        None => Err(NodeError::BufferFull { message: outgoing }),
+    }
 }
 
 fn connect_request(state: &NodeConfig) -> Result<Request<()>, NodeError> {
-    let mut builder = Request::get(format!("ws://{}", state.address));
+    let mut builder = Request::get(state.address.websocket_url());
     builder = builder.header("Authorization", &state.authorization);
     builder = builder.header("User-Id", state.user_id.0);
 
@@ -558,60 +1685,535 @@ fn connect_request(state: &NodeConfig) -> Result<Request<()>, NodeError> {
         .map_err(|source| NodeError::BuildingConnectionRequest { source })
 }
 
-async fn reconnect(config: &NodeConfig) -> Result<WebSocketStream<ConnectStream>, NodeError> {
-    let (mut stream, _) = backoff(config).await?;
+/// Connect to a node, buffering the events that are sent in the meantime.
+///
+/// Returns `None` if connecting was canceled because the node was closed.
+async fn connect_buffered(
+    config: &NodeConfig,
+    buffered: &Mutex<Buffered>,
+    node_from: &mut UnboundedReceiver<OutgoingEvent>,
+) -> Result<Option<Session>, NodeError> {
+    let connecting = reconnect(config);
+    pin_mut!(connecting);
 
-    if let Some(resume) = config.resume.as_ref() {
-        let payload = serde_json::json!({
-            "op": "event-buffer",
-            "timeout": resume.timeout,
-        });
-        let msg = Message::Text(serde_json::to_string(&payload).unwrap());
+    loop {
+        match future::select(connecting.as_mut(), node_from.next()).await {
+            Either::Left((res, _)) => return res.map(Some),
+            Either::Right((Some(outgoing), _)) => {
+                let mut buffered = lock_buffered(buffered);
+
+                // Nobody is waiting for the result of events sent through the
+                // node's sender.
+                if let Err(source) = push_buffered(&mut buffered.events, &config.buffer, outgoing) {
+                    tracing::warn!("{:?}", source);
+                }
+            }
+            Either::Right((None, _)) => {
+                tracing::debug!("node {} closed, no longer connecting", config.address);
 
-        stream.send(msg).await.unwrap();
+                return Ok(None);
+            }
+        }
     }
+}
 
-    Ok(stream)
+/// A connection to a node, and whether it resumed the previous session.
+struct Session {
+    stream: WebSocketStream<ConnectStream>,
+    connection_id: Option<u64>,
+    resumed: bool,
+}
+
+fn parse_connection_id(id: &HeaderValue) -> Result<u64, NodeError> {
+    id.to_str()
+        .map_err(|source| NodeError::ParsingResponseHeader { source })?
+        .parse()
+        .map_err(|source| NodeError::ParsingInt { source })
+}
+
+async fn reconnect(config: &NodeConfig) -> Result<Session, NodeError> {
+    let (stream, res) = backoff(config).await?;
+
+    // A malformed connection id only means that the session can't be resumed
+    // later, so it's treated like a missing one.
+    let connection_id = match res.headers().get("andesite-connection-id") {
+        Some(id) => match parse_connection_id(id) {
+            Ok(id) => Some(id),
+            Err(source) => {
+                tracing::warn!(
+                    "invalid connection id from node {}: {:?}",
+                    config.address,
+                    source
+                );
+
+                None
+            }
+        },
+        None => None,
+    };
+
+    // The server keeps the connection id of a session when it is resumed, and
+    // starts a new session with a new id otherwise.
+    let resume_id = config
+        .resume
+        .as_ref()
+        .and_then(|resume| resume.connection_id);
+    let resumed = resume_id.is_some() && resume_id == connection_id;
+
+    if resume_id.is_some() && !resumed {
+        tracing::debug!("failed to resume session of node {}", config.address);
+    }
+
+    Ok(Session {
+        stream,
+        connection_id,
+        resumed,
+    })
 }
 
 async fn backoff(
     config: &NodeConfig,
 ) -> Result<(WebSocketStream<ConnectStream>, Response<()>), NodeError> {
-    let mut seconds = 1;
+    let mut failed_attempts = 0;
 
     loop {
         let req = connect_request(config)?;
 
-        match async_tungstenite::tokio::connect_async(req).await {
-            Ok((stream, res)) => return Ok((stream, res)),
-            Err(source) => {
-                tracing::warn!("failed to connect to node {}: {:?}", source, config.address);
-
-                if matches!(source, TungsteniteError::Http(ref res) if res.status() == StatusCode::UNAUTHORIZED)
-                {
-                    return Err(NodeError::Unauthorized {
-                        address: config.address,
-                        authorization: config.authorization.to_owned(),
-                    });
-                }
+        let source = match async_tungstenite::tokio::connect_async(req).await {
+            Ok((mut stream, res)) => match event_buffer(config, &mut stream).await {
+                Ok(()) => return Ok((stream, res)),
+                Err(source) => source,
+            },
+            Err(source) => source,
+        };
+
+        tracing::warn!("failed to connect to node {}: {:?}", config.address, source);
+
+        if matches!(source, TungsteniteError::Http(ref res) if res.status() == StatusCode::UNAUTHORIZED)
+        {
+            return Err(NodeError::Unauthorized {
+                address: config.address.clone(),
+                authorization: config.authorization.to_owned(),
+            });
+        }
+
+        failed_attempts += 1;
+
+        let delay = match config.reconnect.delay(failed_attempts) {
+            Some(delay) => delay,
+            None => {
+                tracing::debug!("no longer trying to connect to node {}", config.address);
+
+                return Err(NodeError::Connecting { source });
+            }
+        };
+
+        tracing::debug!(
+            "waiting {:?} before attempting to connect to node {} again",
+            delay,
+            config.address,
+        );
+        sleep(delay).await;
+    }
+}
+
+async fn event_buffer(
+    config: &NodeConfig,
+    stream: &mut WebSocketStream<ConnectStream>,
+) -> Result<(), TungsteniteError> {
+    if let Some(resume) = config.resume.as_ref() {
+        let payload = serde_json::json!({
+            "op": "event-buffer",
+            "timeout": resume.timeout,
+        });
+
+        stream.send(Message::Text(payload.to_string())).await?;
+    }
 
-                if seconds > 64 {
-                    tracing::debug!("no longer trying to connect to node {}", config.address);
+    Ok(())
+}
 
-                    return Err(NodeError::Connecting { source });
+#[cfg(test)]
+mod tests {
+    use super::{
+        push_buffered, BufferOverflow, Node, NodeAddress, NodeAddressError, NodeConfig, NodeError,
+        NodeState, OutgoingBuffer, Reconnect,
+    };
+    use crate::{
+        model::{
+            Destroy, Filters, IncomingEvent, Opcode, Play, PlayerDestroy, PlayerUpdate,
+            PlayerUpdateState, Stats, StatsCpu, StatsMemory, TrackEnd, TrackEndReason,
+            TrackEventType,
+        },
+        player::{CurrentTrack, PlayerManager},
+    };
+    use async_tungstenite::tungstenite::Message;
+    use futures_channel::mpsc::{self, UnboundedSender};
+    use futures_util::{sink::SinkExt, stream::StreamExt};
+    use std::{
+        collections::{HashMap, VecDeque},
+        net::SocketAddr,
+        time::Duration,
+    };
+    use tokio::net::TcpListener;
+    use twilight_model::id::{GuildId, UserId};
+
+    /// Start a server that accepts a single connection and sends it the
+    /// events that are sent to the returned sender.
+    async fn server() -> (SocketAddr, UnboundedSender<IncomingEvent>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (tx, mut rx) = mpsc::unbounded::<IncomingEvent>();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let stream = async_tungstenite::tokio::accept_async(stream)
+                .await
+                .unwrap();
+            let (mut sink, mut source) = stream.split();
+
+            tokio::spawn(async move { while source.next().await.is_some() {} });
+
+            while let Some(event) = rx.next().await {
+                let payload = serde_json::to_string(&event).unwrap();
+
+                if sink.send(Message::Text(payload)).await.is_err() {
+                    break;
                 }
+            }
+        });
 
-                tracing::debug!(
-                    "waiting {} seconds before attempting to connect to node {} again",
-                    seconds,
-                    config.address,
-                );
-                sleep(Duration::from_secs(seconds)).await;
+        (address, tx)
+    }
 
-                seconds *= 2;
+    fn stats() -> Stats {
+        Stats {
+            cpu: StatsCpu {
+                cores: 0,
+                lavalink_load: 0f64,
+                system_load: 0f64,
+            },
+            frames: None,
+            memory: StatsMemory {
+                allocated: 0,
+                free: 0,
+                used: 0,
+                reservable: 0,
+            },
+            players: 0,
+            playing_players: 0,
+            op: Opcode::Stats,
+            uptime: 0,
+        }
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let reconnect = Reconnect::new(Duration::from_secs(1), Duration::from_secs(64), false);
+        let delays = (0..10)
+            .map(|attempts| reconnect.delay(attempts).unwrap().as_secs())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![1, 1, 2, 4, 8, 16, 32, 64, 64, 64], delays);
+        assert_eq!(Some(Duration::from_secs(64)), reconnect.delay(u32::MAX));
+
+        // Overflowing delays are capped too.
+        let reconnect = Reconnect::new(Duration::MAX / 2, Duration::MAX, false);
+        assert_eq!(Some(Duration::MAX), reconnect.delay(3));
+    }
+
+    #[test]
+    fn test_reconnect_delay_attempts() {
+        let reconnect =
+            Reconnect::new_with_attempts(3, Duration::from_secs(1), Duration::from_secs(64), false);
+
+        assert_eq!(Some(Duration::from_secs(2)), reconnect.delay(2));
+        assert!(reconnect.delay(3).is_none());
+        assert!(reconnect.delay(4).is_none());
+    }
+
+    #[test]
+    fn test_reconnect_delay_jitter() {
+        let reconnect = Reconnect::new(Duration::from_secs(1), Duration::from_secs(64), true);
+        let without_jitter = Reconnect {
+            jitter: false,
+            ..reconnect.clone()
+        };
 
-                continue;
+        for attempts in 0..10 {
+            let max = without_jitter.delay(attempts).unwrap();
+
+            for _ in 0..10 {
+                let delay = reconnect.delay(attempts).unwrap();
+
+                assert!(delay >= max / 2 && delay <= max, "{:?}", delay);
             }
         }
     }
+
+    #[test]
+    fn test_node_address_parse() {
+        let address = NodeAddress::parse("localhost:2333").unwrap();
+        assert!(!address.is_secure());
+        assert_eq!("localhost", address.host());
+        assert_eq!(2333, address.port());
+        assert_eq!("", address.path());
+        assert_eq!("ws://localhost:2333/", address.websocket_url());
+
+        let address = NodeAddress::parse("andesite.example.com").unwrap();
+        assert_eq!(NodeAddress::new("andesite.example.com", 80, false), address);
+
+        let address = NodeAddress::parse("wss://andesite.example.com/audio/").unwrap();
+        assert!(address.is_secure());
+        assert_eq!(443, address.port());
+        assert_eq!("/audio", address.path());
+        assert_eq!(
+            "https://andesite.example.com:443/audio/loadtracks",
+            address.http_url("/loadtracks")
+        );
+
+        let address = NodeAddress::parse("https://andesite.example.com:8443").unwrap();
+        assert!(address.is_secure());
+        assert_eq!(8443, address.port());
+
+        let address = NodeAddress::parse("http://127.0.0.1:2333").unwrap();
+        assert_eq!(
+            NodeAddress::from(SocketAddr::from(([127, 0, 0, 1], 2333))),
+            address
+        );
+    }
+
+    #[test]
+    fn test_node_address_parse_ipv6() {
+        let address = NodeAddress::parse("[::1]:2333").unwrap();
+        assert_eq!("[::1]", address.host());
+        assert_eq!(2333, address.port());
+        assert_eq!("ws://[::1]:2333/", address.websocket_url());
+        assert_eq!(
+            NodeAddress::from("[::1]:2333".parse::<SocketAddr>().unwrap()),
+            address
+        );
+
+        let address = NodeAddress::parse("wss://[2001:db8::1]").unwrap();
+        assert_eq!("[2001:db8::1]", address.host());
+        assert_eq!(443, address.port());
+    }
+
+    #[test]
+    fn test_node_address_parse_invalid() {
+        assert!(matches!(
+            NodeAddress::parse("ftp://andesite.example.com"),
+            Err(NodeAddressError::UnsupportedScheme { scheme }) if scheme == "ftp"
+        ));
+
+        for url in &[
+            "",
+            "http://",
+            "localhost:port",
+            "[::1",
+            "http://[::1]:99999",
+        ] {
+            assert!(
+                matches!(
+                    NodeAddress::parse(url),
+                    Err(NodeAddressError::ParsingUrl { .. })
+                ),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_buffer_overflow() {
+        let mut buffer = VecDeque::new();
+        let reject = OutgoingBuffer::new(1, BufferOverflow::Reject);
+        assert!(push_buffered(&mut buffer, &reject, Destroy::new(GuildId(1)).into()).is_ok());
+        assert!(matches!(
+            push_buffered(&mut buffer, &reject, Destroy::new(GuildId(2)).into()),
+            Err(NodeError::BufferFull { message }) if message.guild_id() == GuildId(2)
+        ));
+
+        let drop_oldest = OutgoingBuffer::new(1, BufferOverflow::DropOldest);
+        assert!(push_buffered(&mut buffer, &drop_oldest, Destroy::new(GuildId(3)).into()).is_ok());
+        assert_eq!(1, buffer.len());
+        assert_eq!(
+            Some(GuildId(3)),
+            buffer.front().map(|event| event.guild_id())
+        );
+
+        let coalesce = OutgoingBuffer::new(1, BufferOverflow::Coalesce);
+        assert!(push_buffered(&mut buffer, &coalesce, Destroy::new(GuildId(3)).into()).is_ok());
+        assert!(push_buffered(&mut buffer, &coalesce, Destroy::new(GuildId(4)).into()).is_err());
+    }
+
+    /// Wait until the state of a node is a given state.
+    async fn wait_for(node: &Node, state: NodeState) {
+        let mut states = node.state_changes();
+
+        while *states.borrow() != state {
+            states.changed().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_unreachable() {
+        // Nothing listens on the port once the listener is dropped.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut config = NodeConfig::new(UserId(1), address, "", None);
+        config.reconnect = Reconnect::new_with_attempts(
+            2,
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+            false,
+        );
+
+        let (node, _rx) = Node::connect(config, PlayerManager::new()).await.unwrap();
+        assert_eq!(NodeState::Connecting, node.state());
+
+        // Events are buffered until the node gives up connecting.
+        assert!(node.send(Destroy::new(GuildId(1))).is_ok());
+
+        wait_for(&node, NodeState::Disconnected).await;
+        assert!(matches!(
+            node.send(Destroy::new(GuildId(1))),
+            Err(NodeError::Closed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_close_while_connecting() {
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let config = NodeConfig::new(UserId(1), address, "", None);
+
+        let (node, _rx) = Node::connect(config, PlayerManager::new()).await.unwrap();
+        assert_eq!(NodeState::Connecting, node.state());
+
+        node.close();
+        wait_for(&node, NodeState::Disconnected).await;
+    }
+
+    #[tokio::test]
+    async fn test_events_from_previous_node_ignored() {
+        let guild_id = GuildId(1);
+        let players = PlayerManager::new();
+
+        let (old_address, old_server) = server().await;
+        let (new_address, _new_server) = server().await;
+        let old_config = NodeConfig::new(UserId(2), old_address, "", None);
+        let new_config = NodeConfig::new(UserId(2), new_address, "", None);
+        let (old, mut old_rx) = Node::connect(old_config, players.clone()).await.unwrap();
+        let (new, _new_rx) = Node::connect(new_config, players.clone()).await.unwrap();
+
+        {
+            let mut player = players.get_or_insert(guild_id, old.clone());
+            *player.current_track_mut() = Some(CurrentTrack::new("track"));
+            player.move_to(new.clone()).unwrap();
+        }
+
+        let events = vec![
+            IncomingEvent::PlayerUpdate(PlayerUpdate {
+                op: Opcode::PlayerUpdate,
+                guild_id,
+                user_id: None,
+                state: PlayerUpdateState {
+                    time: 1,
+                    position: Some(1000),
+                    paused: true,
+                    volume: 10,
+                    filters: Filters::default(),
+                    destroyed: None,
+                    mixer: HashMap::new(),
+                    mixer_enabled: false,
+                    frame: None,
+                },
+            }),
+            IncomingEvent::TrackEnd(TrackEnd {
+                op: Opcode::Event,
+                kind: TrackEventType::End,
+                guild_id,
+                user_id: None,
+                track: "track".to_owned(),
+                reason: TrackEndReason::Cleanup,
+            }),
+            IncomingEvent::PlayerDestroy(PlayerDestroy {
+                op: Opcode::Event,
+                kind: TrackEventType::PlayerDestroy,
+                guild_id,
+                user_id: None,
+                cleanup: true,
+            }),
+            IncomingEvent::Stats(stats()),
+        ];
+
+        for event in events {
+            old_server.unbounded_send(event).unwrap();
+        }
+
+        // Events are handled in order, so the ignored events have been handled
+        // once the stats arrive, and they aren't forwarded either.
+        assert!(matches!(old_rx.next().await, Some(IncomingEvent::Stats(_))));
+
+        let player = players.get(&guild_id).unwrap();
+        assert_eq!(new.config().address, player.node().config().address);
+        assert_eq!(
+            Some("track"),
+            player
+                .current_track()
+                .as_ref()
+                .map(|current| current.track.as_str())
+        );
+        assert_ne!(Some(1000), player.position());
+        assert_ne!(10, player.volume());
+        assert!(!player.paused());
+    }
+
+    #[tokio::test]
+    async fn test_end_time_stop_dropped() {
+        let guild_id = GuildId(1);
+        let players = PlayerManager::new();
+
+        let (address, server) = server().await;
+        let config = NodeConfig::new(UserId(2), address, "", None);
+        let (node, mut rx) = Node::connect(config, players.clone()).await.unwrap();
+
+        players
+            .get_or_insert(guild_id, node.clone())
+            .send(Play::new_complex(guild_id, "track", None, 100, false))
+            .unwrap();
+
+        assert!(matches!(
+            rx.next().await,
+            Some(IncomingEvent::TrackEnd(TrackEnd {
+                reason: TrackEndReason::EndTimeReached,
+                ..
+            }))
+        ));
+
+        // The node reports that the track was stopped too, which was already
+        // reported as the end time being reached.
+        server
+            .unbounded_send(IncomingEvent::TrackEnd(TrackEnd {
+                op: Opcode::Event,
+                kind: TrackEventType::End,
+                guild_id,
+                user_id: None,
+                track: "track".to_owned(),
+                reason: TrackEndReason::Stopped,
+            }))
+            .unwrap();
+        server
+            .unbounded_send(IncomingEvent::Stats(stats()))
+            .unwrap();
+
+        assert!(matches!(rx.next().await, Some(IncomingEvent::Stats(_))));
+    }
 }
@@ -10,15 +10,248 @@
 //! [send events]: struct.Player.html#method.send
 //! [read the position]: struct.Player.html#method.position
 
-use crate::{model::*, node::Node};
+#[cfg(feature = "http")]
+use crate::http::TrackInfo;
+use crate::{
+    client::Subscribers,
+    model::*,
+    node::{Node, NodeError},
+    queue::{Advance, Queue, QueueEvent, QueuedTrack, RepeatMode},
+};
 use dashmap::{
     mapref::one::{Ref, RefMut},
     DashMap,
 };
-use futures_channel::mpsc::TrySendError;
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{runtime::Handle, time::sleep};
 use twilight_model::id::GuildId;
 
+/// The maximum volume of a player.
+const MAX_VOLUME: i64 = 1000;
+
+/// The maximum duration between checks of whether a track reached its end
+/// time.
+const END_TIME_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The ID of the mixer player that plays the outgoing track of a crossfade.
+const CROSSFADE_OUTGOING: &str = "crossfade-outgoing";
+
+/// The ID of the mixer player that plays the incoming track of a crossfade.
+const CROSSFADE_INCOMING: &str = "crossfade-incoming";
+
+/// An error that can occur while controlling a player.
+#[derive(Debug)]
+pub enum PlayerError {
+    /// The volume isn't between 0 and 1000.
+    InvalidVolume {
+        /// The provided volume.
+        volume: i64,
+    },
+    /// The position is negative or past the end of the track.
+    PositionOutOfRange {
+        /// The provided position in milliseconds.
+        position: i64,
+        /// The length of the track in milliseconds, if known.
+        length: Option<u64>,
+    },
+    /// The player isn't playing a track, so there is nothing to seek.
+    NothingPlaying,
+    /// A transition or the end time of a track needs a Tokio runtime to be
+    /// timed, but the player wasn't controlled from within one.
+    NoRuntime,
+    /// The track that the player is playing can't be seeked, such as a
+    /// stream.
+    Unseekable,
+    /// The player's node doesn't support a filter.
+    UnsupportedFilter {
+        /// The kind of the filter.
+        filter: FilterKind,
+    },
+    /// Sending the event to the node failed because the node was closed, or
+    /// because it is reconnecting and its buffer is full.
+    Sending {
+        /// The source of the error.
+        source: NodeError,
+    },
+}
+
+impl Display for PlayerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidVolume { volume } => {
+                write!(f, "volume {} isn't between 0 and {}", volume, MAX_VOLUME)
+            }
+            Self::PositionOutOfRange {
+                position,
+                length: Some(length),
+            } => write!(f, "position {} isn't between 0 and {}", position, length),
+            Self::PositionOutOfRange { position, .. } => {
+                write!(f, "position {} is negative", position)
+            }
+            Self::NothingPlaying => f.write_str("player isn't playing a track"),
+            Self::NoRuntime => f.write_str("no tokio runtime is running"),
+            Self::Unseekable => f.write_str("track can't be seeked"),
+            Self::UnsupportedFilter { filter } => {
+                write!(f, "node doesn't support the {} filter", filter.as_str())
+            }
+            Self::Sending { .. } => f.write_str("couldn't send event to node"),
+        }
+    }
+}
+
+impl Error for PlayerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Sending { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Options for playing a track via [`Player::play_with`].
+///
+/// [`Player::play_with`]: struct.Player.html#method.play_with
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlayOptions {
+    /// The position in milliseconds to start the track from.
+    pub start_time: Option<u64>,
+    /// The position in milliseconds to end the track at.
+    pub end_time: Option<u64>,
+    /// Whether to keep playing the current track, if any, instead of
+    /// replacing it.
+    pub no_replace: bool,
+}
+
+impl PlayOptions {
+    /// Create new options that play a track from the start, replacing the
+    /// current track.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the position in milliseconds to start the track from.
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Set the position in milliseconds to end the track at.
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// Set whether to keep playing the current track, if any, instead of
+    /// replacing it.
+    pub fn no_replace(mut self, no_replace: bool) -> Self {
+        self.no_replace = no_replace;
+
+        self
+    }
+}
+
+/// A transition that a [`Player`] applies when it plays or stops tracks.
+///
+/// Tracks that are played fade in from silence, and tracks that are stopped
+/// fade out before stopping. When a track is played over another, the current
+/// track fades out before the new track fades in, or both overlap with a
+/// crossfade if the node [supports the mixer].
+///
+/// Refer to [`Player::set_transition`] to configure it.
+///
+/// [`Player`]: struct.Player.html
+/// [`Player::set_transition`]: struct.Player.html#method.set_transition
+/// [supports the mixer]: ../node/struct.NodeConfig.html#structfield.mixer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Transition {
+    /// The duration of each fade.
+    pub duration: Duration,
+    /// The interval between volume changes during a fade.
+    pub interval: Duration,
+    /// Whether to overlap the current and new tracks via the mixer, where
+    /// available.
+    pub crossfade: bool,
+}
+
+impl Transition {
+    /// Create a new transition that fades over the given duration, changing
+    /// the volume every 100 milliseconds and crossfading where possible.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            interval: Duration::from_millis(100),
+            crossfade: true,
+        }
+    }
+
+    /// Set the interval between volume changes during a fade.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+
+        self
+    }
+
+    /// Set whether to overlap the current and new tracks via the mixer, where
+    /// available.
+    pub fn crossfade(mut self, crossfade: bool) -> Self {
+        self.crossfade = crossfade;
+
+        self
+    }
+
+    /// Return the number of volume changes during a fade.
+    fn steps(&self) -> i64 {
+        let interval = self.interval.as_millis().max(1);
+
+        (self.duration.as_millis() / interval).max(1) as i64
+    }
+}
+
+/// A volume change of a player or mixer player during a transition.
+#[derive(Clone, Debug)]
+struct Ramp {
+    player_id: Option<&'static str>,
+    from: i64,
+    to: i64,
+}
+
+impl Ramp {
+    /// Return the volume at a step of the ramp.
+    fn volume(&self, step: i64, steps: i64) -> i64 {
+        self.from + (self.to - self.from) * step / steps
+    }
+}
+
+/// The transition configuration of a player and the state of its running
+/// transition.
+#[derive(Debug, Default)]
+struct Transitions {
+    config: Option<Transition>,
+    generation: usize,
+    volume: Option<i64>,
+}
+
+/// The scheduled end of the track that a player is playing.
+#[derive(Debug, Default)]
+struct Schedule {
+    generation: usize,
+    end_time: Option<u64>,
+    /// The track after which the queue advanced before it ended, to
+    /// transition into the next track.
+    advanced: Option<String>,
+    /// The track that the player stopped at its end time, whose end the node
+    /// is yet to report.
+    ended: Option<String>,
+}
+
 /// Retrieve and create players for guilds.
 ///
 /// The player manager contains all of the players for all guilds over all
@@ -26,6 +259,7 @@ use twilight_model::id::GuildId;
 #[derive(Clone, Debug, Default)]
 pub struct PlayerManager {
     pub(crate) players: Arc<DashMap<GuildId, Player>>,
+    pub(crate) subscribers: Subscribers,
 }
 
 impl PlayerManager {
@@ -40,7 +274,7 @@ impl PlayerManager {
     }
 
     /// Return a mutable reference to a player by guild ID.
-    pub(crate) fn get_mut(&self, guild_id: &GuildId) -> Option<RefMut<'_, GuildId, Player>> {
+    pub fn get_mut(&self, guild_id: &GuildId) -> Option<RefMut<'_, GuildId, Player>> {
         self.players.get_mut(guild_id)
     }
 
@@ -49,7 +283,7 @@ impl PlayerManager {
     pub fn get_or_insert(&self, guild_id: GuildId, node: Node) -> RefMut<'_, GuildId, Player> {
         self.players
             .entry(guild_id)
-            .or_insert_with(|| Player::new(guild_id, node))
+            .or_insert_with(|| Player::new(guild_id, node, self.subscribers.clone()))
     }
 
     /// Remove a player by guild ID.
@@ -58,44 +292,234 @@ impl PlayerManager {
     }
 }
 
+/// The track that a player is playing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurrentTrack {
+    /// The base64 track.
+    pub track: String,
+    /// The decoded information of the track, if it could be decoded.
+    #[cfg(feature = "http")]
+    pub info: Option<TrackInfo>,
+}
+
+impl CurrentTrack {
+    /// Create a new current track, decoding its information.
+    pub fn new(track: impl Into<String>) -> Self {
+        let track = track.into();
+
+        #[cfg(feature = "http")]
+        let info = match crate::track::decode(&track) {
+            Ok(info) => Some(info),
+            Err(source) => {
+                tracing::debug!("couldn't decode track {}: {:?}", track, source);
+
+                None
+            }
+        };
+
+        Self {
+            track,
+            #[cfg(feature = "http")]
+            info,
+        }
+    }
+
+    /// The length of the track in milliseconds, if it is known and the track
+    /// isn't a stream.
+    pub(crate) fn length(&self) -> Option<u64> {
+        #[cfg(feature = "http")]
+        return self
+            .info
+            .as_ref()
+            .filter(|info| !info.is_stream)
+            .map(|info| info.length);
+
+        #[cfg(not(feature = "http"))]
+        None
+    }
+
+    /// Whether the track is known to be a stream or otherwise unseekable.
+    pub(crate) fn is_unseekable(&self) -> bool {
+        #[cfg(feature = "http")]
+        return self
+            .info
+            .as_ref()
+            .is_some_and(|info| info.is_stream || !info.is_seekable);
+
+        #[cfg(not(feature = "http"))]
+        false
+    }
+}
+
+/// The playback status of a player.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum PlayerStatus {
+    /// The player isn't playing a track.
+    #[default]
+    Idle,
+    /// The player is playing a track.
+    Playing,
+    /// The player is paused while playing a track.
+    Paused,
+    /// The track that the player is playing got stuck, or the last track got
+    /// stuck before it ended.
+    Stuck,
+    /// The track that the player is playing encountered an exception, or the
+    /// last track encountered one before it ended.
+    Errored,
+}
+
+/// The duration after which changes that a node didn't confirm are discarded
+/// in favour of its player updates.
+const PENDING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum difference in milliseconds between a sent position and a
+/// reported position for the position to be considered confirmed.
+const POSITION_TOLERANCE: i64 = 1000;
+
+/// The maximum delay between a node reporting a player's state and the report
+/// being received for the node's clock to be trusted.
+const MAX_LATENCY: Duration = Duration::from_secs(5);
+
+/// The state of a player as last confirmed by its node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerState {
+    /// The Unix timestamp in milliseconds of the node when the state was
+    /// reported.
+    pub time: i64,
+    /// The position of the track in milliseconds.
+    pub position: Option<i64>,
+    /// Whether the player is paused.
+    pub paused: bool,
+    /// The volume of the player from 0 to 1000.
+    pub volume: i64,
+    /// The filters of the player.
+    pub filters: Filters,
+    /// The track that the player is playing, if any.
+    pub current_track: Option<CurrentTrack>,
+    /// The playback status of the player.
+    pub status: PlayerStatus,
+}
+
+/// Changes sent to a player's node that the node hasn't confirmed yet.
+///
+/// Changes are confirmed once a player update or track event reflects them,
+/// and are discarded if the node doesn't confirm them within a few seconds. A
+/// played track stays pending until the node reports that it started, ended
+/// or failed, however long it takes to load.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PendingState {
+    /// The track that was sent to be played, or `Some(None)` if the player
+    /// was stopped.
+    pub track: Option<Option<CurrentTrack>>,
+    /// Whether the player was paused or resumed.
+    pub paused: Option<bool>,
+    /// The position that the player seeked to.
+    pub position: Option<i64>,
+    /// The volume that was set.
+    pub volume: Option<i64>,
+    /// The filters that were set.
+    pub filters: Option<Filters>,
+}
+
+impl PendingState {
+    /// Whether there are no pending changes.
+    pub fn is_empty(&self) -> bool {
+        self.track.is_none()
+            && self.paused.is_none()
+            && self.position.is_none()
+            && self.volume.is_none()
+            && self.filters.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    state: PendingState,
+    sent_at: Option<Instant>,
+}
+
 /// A player for a guild connected to a node.
 ///
 /// This can be used to send events over a node and to read the details of a
 /// player for a guild.
+///
+/// Events sent via the player are applied to its state right away, before the
+/// node confirms them. Use [`pending`] to retrieve the changes that haven't
+/// been confirmed yet, and [`confirmed`] to retrieve the state as last
+/// reported by the node.
+///
+/// [`confirmed`]: #method.confirmed
+/// [`pending`]: #method.pending
 #[derive(Debug)]
 pub struct Player {
     guild_id: GuildId,
     node: Node,
     time: i64,
+    updated_at: Option<Instant>,
     position: Option<i64>,
     paused: bool,
     volume: i64,
     filters: Filters,
+    current_track: Option<CurrentTrack>,
+    status: PlayerStatus,
+    voice_update: Option<VoiceUpdate>,
+    mixer_enabled: bool,
+    mixer: HashMap<String, MixerPlayerState>,
+    pending: Mutex<Pending>,
+    queue: Mutex<Queue>,
+    transitions: Mutex<Transitions>,
+    schedule: Mutex<Schedule>,
+    subscribers: Subscribers,
 }
 
 impl Player {
-    pub(crate) fn new(guild_id: GuildId, node: Node) -> Self {
+    pub(crate) fn new(guild_id: GuildId, node: Node, subscribers: Subscribers) -> Self {
         Self {
             guild_id,
             node,
             time: 0,
+            updated_at: None,
             position: None,
             paused: false,
-            volume: 0,
+            volume: 100,
             filters: Filters::default(),
+            current_track: None,
+            status: PlayerStatus::Idle,
+            voice_update: None,
+            mixer_enabled: false,
+            mixer: HashMap::new(),
+            pending: Mutex::default(),
+            queue: Mutex::default(),
+            transitions: Mutex::default(),
+            schedule: Mutex::default(),
+            subscribers,
         }
     }
 
     /// Send an event to the player's node.
     ///
-    /// Returns a `futures_channel` `TrySendError` if the node has been removed.
+    /// [`Play`], [`Update`], [`Stop`], and [`Destroy`] events are applied to
+    /// the player's state as [pending] changes once sent, unless they target a
+    /// mixer player. Filters of an [`Update`] event that the player's node
+    /// doesn't support are left out if they don't alter the audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::NoRuntime`] if a [`Play`] event has an end time
+    /// but no Tokio runtime is running.
+    ///
+    /// Returns [`PlayerError::UnsupportedFilter`] if a filter of an [`Update`]
+    /// event alters the audio but the player's node doesn't [support] it.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
     ///
     /// # Examples
     ///
-    /// Send a [`Play`] and [`Pause`] event:
+    /// Send a [`Play`] and a pausing [`Update`] event:
     ///
     /// ```
-    /// use twilight_lavalink::{model::{Play, Pause}, Lavalink};
+    /// use twilight_andesite::{model::{Play, Update}, Lavalink};
     /// # use twilight_model::id::{GuildId, UserId};
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let (guild_id, user_id) = (GuildId(1), UserId(2));
@@ -105,85 +529,1953 @@ impl Player {
     /// let players = lavalink.players();
     ///
     /// if let Some(player) = players.get(&guild_id) {
-    ///     player.send(Play::from((guild_id, track)))?;
-    ///     player.send(Pause::from((guild_id, true)))?;
+    ///     player.send(Play::new(guild_id, track))?;
+    ///     player.send(Update::new(guild_id, true, None, None, None))?;
     /// }
     /// # Ok(()) }
     /// ```
     ///
-    /// [`Pause`]: ../model/outgoing/struct.Pause.html
+    /// [`Destroy`]: ../model/outgoing/struct.Destroy.html
     /// [`Play`]: ../model/outgoing/struct.Play.html
-    pub fn send(&self, event: impl Into<OutgoingEvent>) -> Result<(), TrySendError<OutgoingEvent>> {
+    /// [`PlayerError::NoRuntime`]: enum.PlayerError.html#variant.NoRuntime
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [`PlayerError::UnsupportedFilter`]: enum.PlayerError.html#variant.UnsupportedFilter
+    /// [`Stop`]: ../model/outgoing/struct.Stop.html
+    /// [`Update`]: ../model/outgoing/struct.Update.html
+    /// [pending]: #method.pending
+    /// [support]: ../node/struct.NodeConfig.html#structfield.filters
+    pub fn send(&self, event: impl Into<OutgoingEvent>) -> Result<(), PlayerError> {
         self._send(event.into())
     }
 
-    fn _send(&self, event: OutgoingEvent) -> Result<(), TrySendError<OutgoingEvent>> {
+    fn _send(&self, mut event: OutgoingEvent) -> Result<(), PlayerError> {
+        if let OutgoingEvent::Update(update) = &mut event {
+            if let Some(filters) = update.filters.take() {
+                update.filters = Some(self.supported_filters(filters)?);
+            }
+        }
+
         tracing::debug!(
             "sending event on guild player {}: {:?}",
             self.guild_id,
             event
         );
 
-        self.node.send(event)
+        // The end time of a track is enforced on a runtime.
+        let runtime = match &event {
+            OutgoingEvent::Play(play) if play.end_time.is_some() && play.player_id.is_none() => {
+                Some(runtime()?)
+            }
+            _ => None,
+        };
+
+        self.node
+            .send(event.clone())
+            .map_err(|source| PlayerError::Sending { source })?;
+        self.apply(&event, runtime.as_ref());
+
+        Ok(())
     }
 
-    /// Return an immutable reference to the node linked to the player.
-    pub fn node(&self) -> &Node {
-        &self.node
+    /// Apply a sent event to the pending state, scheduling the end of a played
+    /// track on the runtime if it has an end time.
+    fn apply(&self, event: &OutgoingEvent, runtime: Option<&Handle>) {
+        let mixer_player = match event {
+            OutgoingEvent::Play(play) => play.player_id.is_some(),
+            OutgoingEvent::Update(update) => update.player_id.is_some(),
+            OutgoingEvent::Stop(stop) => stop.player_id.is_some(),
+            OutgoingEvent::Destroy(destroy) => destroy.player_id.is_some(),
+            _ => false,
+        };
+
+        if mixer_player {
+            return;
+        }
+
+        let playing = self.current_track().is_some();
+        let mut filters = self.filters();
+        let mut pending = self.lock_pending();
+
+        match event {
+            OutgoingEvent::Play(play) => {
+                if play.no_replace && playing {
+                    return;
+                }
+
+                pending.state.track = Some(Some(CurrentTrack::new(play.track.as_str())));
+                pending.state.position = Some(play.start_time.unwrap_or(0) as i64);
+            }
+            OutgoingEvent::Update(update) => {
+                if let Some(paused) = update.pause {
+                    pending.state.paused = Some(paused);
+                }
+
+                if let Some(position) = update.position {
+                    pending.state.position = Some(position);
+                }
+
+                if let Some(volume) = update.volume {
+                    pending.state.volume = Some(volume);
+                }
+
+                if let Some(changes) = update.filters.as_ref() {
+                    filters.apply(changes);
+                    pending.state.filters = Some(filters);
+                }
+            }
+            OutgoingEvent::Stop(_) | OutgoingEvent::Destroy(_) => {
+                pending.state.track = Some(None);
+                pending.state.position = None;
+            }
+            _ => return,
+        }
+
+        pending.sent_at = Some(Instant::now());
+        drop(pending);
+
+        match event {
+            OutgoingEvent::Play(play) => self.schedule_end(play, runtime),
+            OutgoingEvent::Stop(_) | OutgoingEvent::Destroy(_) => self.cancel_end(),
+            _ => {}
+        }
     }
 
-    /// Return a copy of the player's guild ID.
-    pub fn guild_id(&self) -> GuildId {
-        self.guild_id
+    /// Schedule the end of a played track at its end time, if any, replacing
+    /// the scheduled end of the previous track.
+    ///
+    /// Nodes don't stop tracks at their end time, so the player watches the
+    /// track's [estimated position] instead. Once the end time is reached, the
+    /// track is stopped and a [`TrackEnd`] event is emitted with the
+    /// [`EndTimeReached`] reason, after which the next track of the queue is
+    /// played like after any other track that ended. The event that the node
+    /// sends once the track is stopped is dropped, so that the end of the
+    /// track is only reported once.
+    ///
+    /// If the position isn't known yet, such as right after the track was
+    /// played, the track is assumed to have played from its start time since
+    /// it was sent.
+    ///
+    /// If the player has a [transition] and the track was played from the
+    /// queue, the next track of the queue is played the transition's duration
+    /// before the track ends instead, so that the track fades or crossfades
+    /// into it.
+    ///
+    /// [`EndTimeReached`]: ../model/incoming/enum.TrackEndReason.html#variant.EndTimeReached
+    /// [`TrackEnd`]: ../model/incoming/struct.TrackEnd.html
+    /// [estimated position]: #method.estimated_position
+    /// [transition]: #method.set_transition
+    fn schedule_end(&self, play: &Play, runtime: Option<&Handle>) {
+        let generation = {
+            let mut schedule = self.lock_schedule();
+            schedule.generation = schedule.generation.wrapping_add(1);
+            schedule.end_time = play.end_time;
+
+            schedule.generation
+        };
+
+        // Transitions are only run on a runtime anyway.
+        let runtime = match (play.end_time, runtime) {
+            (Some(_), Some(runtime)) => runtime.clone(),
+            _ if self.transition().is_some() => match Handle::try_current() {
+                Ok(runtime) => runtime,
+                Err(_) => return,
+            },
+            _ => return,
+        };
+
+        let guild_id = self.guild_id;
+        let players = self.node.players().clone();
+        let track = play.track.clone();
+        let end_time = play.end_time.map(|end_time| end_time as i64);
+        let start_time = play.start_time.unwrap_or(0) as i64;
+        let sent_at = Instant::now();
+
+        runtime.spawn(async move {
+            let transition = loop {
+                let remaining = {
+                    let player = match players.get(&guild_id) {
+                        Some(player) => player,
+                        None => return,
+                    };
+
+                    if !player.is_scheduled(generation, &track) {
+                        return;
+                    }
+
+                    let position = player
+                        .estimated_position()
+                        .unwrap_or_else(|| start_time + sent_at.elapsed().as_millis() as i64);
+
+                    let length = player
+                        .current_track()
+                        .and_then(|current| current.length())
+                        .map(|length| length as i64);
+                    let end = match (end_time, length) {
+                        (Some(end_time), Some(length)) => Some(end_time.min(length)),
+                        (end_time, length) => end_time.or(length),
+                    };
+                    let transition_at = player
+                        .transition()
+                        .zip(end)
+                        .map(|(transition, end)| end - transition.duration.as_millis() as i64);
+
+                    if transition_at.is_some_and(|at| position >= at)
+                        && player.has_next_queued(&track)
+                    {
+                        break true;
+                    }
+
+                    if end_time.is_some_and(|end_time| position >= end_time) {
+                        break false;
+                    }
+
+                    if end.is_none_or(|end| position >= end) {
+                        return;
+                    }
+
+                    [transition_at, end_time]
+                        .iter()
+                        .flatten()
+                        .map(|at| at - position)
+                        .filter(|remaining| *remaining > 0)
+                        .min()
+                };
+
+                let remaining = remaining.map_or(END_TIME_INTERVAL, |remaining| {
+                    Duration::from_millis(remaining as u64).min(END_TIME_INTERVAL)
+                });
+
+                sleep(remaining).await;
+            };
+
+            let node = {
+                let player = match players.get(&guild_id) {
+                    Some(player) => player,
+                    None => return,
+                };
+
+                if !player.is_scheduled(generation, &track) {
+                    return;
+                }
+
+                if transition {
+                    tracing::debug!(
+                        "track of guild player {} transitions into the next track",
+                        guild_id
+                    );
+
+                    player.lock_schedule().advanced = Some(track);
+
+                    if let Err(source) = player.advance(Advance::Finished) {
+                        tracing::warn!(
+                            "couldn't play next track in guild {}: {}",
+                            guild_id,
+                            source
+                        );
+                    }
+
+                    return;
+                }
+
+                tracing::debug!("track of guild player {} reached its end time", guild_id);
+
+                player.lock_schedule().ended = Some(track.clone());
+
+                if let Err(source) = player.send(Stop::new(guild_id)) {
+                    tracing::warn!("couldn't stop guild player {}: {}", guild_id, source);
+                    player.lock_schedule().ended = None;
+
+                    return;
+                }
+
+                player.node().clone()
+            };
+
+            node.end_track(TrackEnd {
+                op: Opcode::Event,
+                kind: TrackEventType::End,
+                guild_id,
+                user_id: None,
+                track,
+                reason: TrackEndReason::EndTimeReached,
+            });
+        });
     }
 
-    /// Return a copy of the player's time.
-    pub fn time(&self) -> i64 {
-        self.time
+    /// Cancel the scheduled end of the current track, if any.
+    fn cancel_end(&self) {
+        let mut schedule = self.lock_schedule();
+        schedule.generation = schedule.generation.wrapping_add(1);
+        schedule.end_time = None;
     }
 
-    /// Return a mutable reference to the player's time.
-    pub(crate) fn time_mut(&mut self) -> &mut i64 {
-        &mut self.time
+    /// Whether the end of a track is still scheduled and the track is still
+    /// playing.
+    fn is_scheduled(&self, generation: usize, track: &str) -> bool {
+        self.lock_schedule().generation == generation
+            && self
+                .current_track()
+                .is_some_and(|current| current.track == track)
     }
 
-    /// Return a copy of the player's position.
-    pub fn position(&self) -> Option<i64> {
-        self.position
+    /// Whether the queue advanced past a track that ended before it ended, to
+    /// transition into the next track, in which case the queue mustn't
+    /// advance again.
+    pub(crate) fn advanced_past(&self, track: &str) -> bool {
+        let mut schedule = self.lock_schedule();
+
+        if schedule.advanced.as_deref() != Some(track) {
+            return false;
+        }
+
+        schedule.advanced = None;
+
+        true
     }
 
-    /// Return a mutable reference to the player's position.
-    pub(crate) fn position_mut(&mut self) -> &mut Option<i64> {
-        &mut self.position
+    /// Whether the node reports the end of a track that the player already
+    /// ended itself at its end time, in which case the end mustn't be reported
+    /// again.
+    pub(crate) fn ended_at_end_time(&self, end: &TrackEnd) -> bool {
+        let mut schedule = self.lock_schedule();
+
+        if end.reason != TrackEndReason::Stopped || schedule.ended.as_ref() != Some(&end.track) {
+            return false;
+        }
+
+        schedule.ended = None;
+
+        true
     }
 
-    /// Return a copy of whether the player is paused.
-    pub fn paused(&self) -> bool {
-        self.paused
+    /// Forget the track that the player stopped at its end time once another
+    /// track started, since the node reported its end before that.
+    pub(crate) fn forget_ended(&self) {
+        self.lock_schedule().ended = None;
     }
 
-    /// Return a mutable copy of whether the player is paused.
-    pub(crate) fn paused_mut(&mut self) -> &mut bool {
-        &mut self.paused
+    fn lock_schedule(&self) -> MutexGuard<'_, Schedule> {
+        self.schedule.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Return a copy of the player's volume.
-    pub fn volume(&self) -> i64 {
-        self.volume
+    /// Reconcile the pending state with the confirmed state after a player
+    /// update.
+    ///
+    /// Pending changes are discarded once the confirmed state reflects them,
+    /// or once they have been pending for too long. The pending track is only
+    /// confirmed by track events, since tracks may take longer to load, unless
+    /// the player was stopped while it wasn't playing anything.
+    pub(crate) fn reconcile(&mut self) {
+        let pending = self
+            .pending
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if pending.state.track == Some(None) && self.current_track.is_none() {
+            pending.state.track = None;
+        }
+
+        let elapsed = match pending.sent_at {
+            Some(sent_at) if sent_at.elapsed() < PENDING_TIMEOUT => sent_at.elapsed(),
+            _ => {
+                pending.state = PendingState {
+                    track: pending.state.track.take(),
+                    ..PendingState::default()
+                };
+                pending.sent_at = None;
+
+                return;
+            }
+        };
+
+        let state = &mut pending.state;
+
+        if state.paused == Some(self.paused) {
+            state.paused = None;
+        }
+
+        if state.volume == Some(self.volume) {
+            state.volume = None;
+        }
+
+        if let Some(filters) = state.filters.as_ref() {
+            let mut applied = self.filters.clone();
+            applied.apply(filters);
+
+            if applied == self.filters {
+                state.filters = None;
+            }
+        }
+
+        if let (Some(sent), Some(reported)) = (state.position, self.position) {
+            let elapsed = elapsed.as_millis() as i64;
+
+            if reported >= sent - POSITION_TOLERANCE
+                && reported <= sent + elapsed + POSITION_TOLERANCE
+            {
+                state.position = None;
+            }
+        }
+
+        if state.is_empty() {
+            pending.sent_at = None;
+        }
     }
 
-    /// Return a mutable reference to the player's volume.
-    pub(crate) fn volume_mut(&mut self) -> &mut i64 {
-        &mut self.volume
+    /// Confirm the pending track once the node reports that it started or
+    /// stopped playing.
+    pub(crate) fn confirm_track(&mut self) {
+        let pending = self
+            .pending
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let confirmed = match pending.state.track.as_ref() {
+            Some(Some(track)) => self.current_track.as_ref() == Some(track),
+            Some(None) => self.current_track.is_none(),
+            None => false,
+        };
+
+        if confirmed {
+            pending.state.track = None;
+        }
     }
 
-    /// Return a copy of the player's filters.
-    pub fn filters(&self) -> Filters {
-        self.filters.clone()
+    /// Discard the pending track if it failed to play, in which case the node
+    /// may never report that it started.
+    pub(crate) fn fail_track(&mut self, track: &str) {
+        let pending = self
+            .pending
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(Some(pending_track)) = pending.state.track.as_ref() {
+            if pending_track.track == track {
+                pending.state.track = None;
+            }
+        }
     }
 
-    /// Return a mutable copy of the player's filters.
-    pub(crate) fn filters_mut(&mut self) -> &mut Filters {
-        &mut self.filters
+    fn lock_pending(&self) -> MutexGuard<'_, Pending> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Return a copy of the changes that were sent to the player's node but
+    /// haven't been confirmed yet.
+    pub fn pending(&self) -> PendingState {
+        self.lock_pending().state.clone()
+    }
+
+    /// Return a copy of the state of the player as last confirmed by its
+    /// node.
+    pub fn confirmed(&self) -> PlayerState {
+        PlayerState {
+            time: self.time,
+            position: self.position,
+            paused: self.paused,
+            volume: self.volume,
+            filters: self.filters.clone(),
+            current_track: self.current_track.clone(),
+            status: self.status,
+        }
+    }
+
+    /// Play a track, replacing the current track, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::NoRuntime`] if a [transition] is configured but
+    /// no Tokio runtime is running.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::NoRuntime`]: enum.PlayerError.html#variant.NoRuntime
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [transition]: #method.set_transition
+    pub fn play(&self, track: impl Into<String>) -> Result<(), PlayerError> {
+        self.play_with(track, PlayOptions::new())
+    }
+
+    /// Play a track with options, such as the position to start from.
+    ///
+    /// If a [transition] is configured, the track fades in, and replaces the
+    /// current track after it fades out or with a crossfade.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::PositionOutOfRange`] if the start or end time is
+    /// past the end of the track, or if the end time is before the start time.
+    ///
+    /// Returns [`PlayerError::NoRuntime`] if a transition is configured or an
+    /// end time is given, but no Tokio runtime is running.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::NoRuntime`]: enum.PlayerError.html#variant.NoRuntime
+    /// [`PlayerError::PositionOutOfRange`]: enum.PlayerError.html#variant.PositionOutOfRange
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [transition]: #method.set_transition
+    pub fn play_with(
+        &self,
+        track: impl Into<String>,
+        options: PlayOptions,
+    ) -> Result<(), PlayerError> {
+        let track = track.into();
+        validate_play(&track, &options)?;
+
+        let play = Play::new_complex(
+            self.guild_id,
+            track,
+            options.start_time,
+            options.end_time,
+            options.no_replace,
+        );
+
+        match self.transition() {
+            Some(transition) if !options.no_replace || self.current_track().is_none() => {
+                self.transition_to(play, transition)
+            }
+            _ => self.send(play),
+        }
+    }
+
+    /// Pause or resume the player.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn pause(&self, paused: bool) -> Result<(), PlayerError> {
+        self.send(Update::new(self.guild_id, paused, None, None, None))
+    }
+
+    /// Seek to a position in milliseconds of the current track.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::NothingPlaying`] if the player isn't playing a
+    /// track.
+    ///
+    /// Returns [`PlayerError::Unseekable`] if the track can't be seeked.
+    ///
+    /// Returns [`PlayerError::PositionOutOfRange`] if the position is negative
+    /// or past the end of the track.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::NothingPlaying`]: enum.PlayerError.html#variant.NothingPlaying
+    /// [`PlayerError::PositionOutOfRange`]: enum.PlayerError.html#variant.PositionOutOfRange
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [`PlayerError::Unseekable`]: enum.PlayerError.html#variant.Unseekable
+    pub fn seek(&self, position: i64) -> Result<(), PlayerError> {
+        let track = self.current_track().ok_or(PlayerError::NothingPlaying)?;
+
+        if track.is_unseekable() {
+            return Err(PlayerError::Unseekable);
+        }
+
+        let length = track.length();
+
+        if position < 0 || length.is_some_and(|length| position as u64 > length) {
+            return Err(PlayerError::PositionOutOfRange { position, length });
+        }
+
+        self.send(Update::new(self.guild_id, None, position, None, None))
+    }
+
+    /// Set the volume of the player from 0 to 1000. 100 is the default.
+    ///
+    /// This cancels the running transition, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::InvalidVolume`] if the volume isn't between 0
+    /// and 1000.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::InvalidVolume`]: enum.PlayerError.html#variant.InvalidVolume
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn set_volume(&self, volume: i64) -> Result<(), PlayerError> {
+        if !(0..=MAX_VOLUME).contains(&volume) {
+            return Err(PlayerError::InvalidVolume { volume });
+        }
+
+        self.cancel_transition();
+        self.send(Update::new(self.guild_id, None, None, volume, None))
+    }
+
+    /// Set the filters of the player.
+    ///
+    /// Filters that aren't set are left unchanged. Use [`Filters::reset`] to
+    /// reset every filter. Filters that the player's node doesn't support are
+    /// left out if they don't alter the audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::UnsupportedFilter`] if a filter alters the audio
+    /// but the player's node doesn't [support] it.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`Filters::reset`]: ../model/outgoing/struct.Filters.html#method.reset
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [`PlayerError::UnsupportedFilter`]: enum.PlayerError.html#variant.UnsupportedFilter
+    /// [support]: ../node/struct.NodeConfig.html#structfield.filters
+    pub fn set_filters(&self, filters: Filters) -> Result<(), PlayerError> {
+        self.send(Update::new(self.guild_id, None, None, None, filters))
+    }
+
+    /// Leave out filters that the player's node doesn't support, failing if
+    /// any of them alters the audio.
+    fn supported_filters(&self, mut filters: Filters) -> Result<Filters, PlayerError> {
+        let supported = &self.node.config().filters;
+
+        for filter in filters.kinds() {
+            if supported.contains(&filter) {
+                continue;
+            }
+
+            if filters.is_enabled(filter) {
+                return Err(PlayerError::UnsupportedFilter { filter });
+            }
+
+            filters.unset(filter);
+        }
+
+        Ok(filters)
+    }
+
+    /// Stop playing the current track.
+    ///
+    /// If a [transition] is configured, the track fades out before it's
+    /// stopped, after which the volume is restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::NoRuntime`] if a transition is configured but no
+    /// Tokio runtime is running.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::NoRuntime`]: enum.PlayerError.html#variant.NoRuntime
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [transition]: #method.set_transition
+    pub fn stop(&self) -> Result<(), PlayerError> {
+        match self.transition() {
+            Some(transition) if self.current_track().is_some() => {
+                let runtime = runtime()?;
+                let volume = self.begin_transition();
+                let guild_id = self.guild_id;
+
+                self.spawn_ramp(
+                    &runtime,
+                    transition,
+                    vec![Ramp {
+                        player_id: None,
+                        from: volume,
+                        to: 0,
+                    }],
+                    move |player| {
+                        player.send(Stop::new(guild_id))?;
+                        player.send(Update::new(guild_id, None, None, volume, None))
+                    },
+                );
+
+                Ok(())
+            }
+            _ => {
+                self.cancel_transition();
+                self.send(Stop::new(self.guild_id))
+            }
+        }
+    }
+
+    /// Destroy the player on its node.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn destroy(&self) -> Result<(), PlayerError> {
+        self.cancel_transition();
+        self.send(Destroy::new(self.guild_id))
+    }
+
+    /// Return a copy of the transition that the player applies when it plays
+    /// or stops tracks, if any.
+    pub fn transition(&self) -> Option<Transition> {
+        self.lock_transitions().config
+    }
+
+    /// Set the transition that the player applies when it plays or stops
+    /// tracks, or `None` to play and stop tracks abruptly.
+    ///
+    /// Tracks played from the player's queue use the transition too, so
+    /// skipping a track crossfades into the next one, and tracks that end
+    /// crossfade into the next one the transition's duration before their
+    /// end.
+    ///
+    /// Transitions change the volume over time, so the player must be
+    /// controlled from within a Tokio runtime while one is configured.
+    pub fn set_transition(&self, transition: impl Into<Option<Transition>>) {
+        self.lock_transitions().config = transition.into();
+    }
+
+    /// Play a track according to a transition.
+    fn transition_to(&self, play: Play, transition: Transition) -> Result<(), PlayerError> {
+        let runtime = runtime()?;
+
+        let current = match self.current_track() {
+            Some(current) => current,
+            None => {
+                let volume = self.begin_transition();
+
+                return self.fade_in(&runtime, play, transition, volume);
+            }
+        };
+
+        let volume = self.begin_transition();
+
+        if transition.crossfade && self.node.config().mixer && !self.mixer_enabled && !self.paused()
+        {
+            return self.crossfade(&runtime, current, play, transition, volume);
+        }
+
+        self.spawn_ramp(
+            &runtime,
+            transition,
+            vec![Ramp {
+                player_id: None,
+                from: volume,
+                to: 0,
+            }],
+            {
+                let runtime = runtime.clone();
+
+                move |player| {
+                    player.begin_transition();
+                    player.fade_in(&runtime, play, transition, volume)
+                }
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Play a track from silence and ramp the volume up.
+    fn fade_in(
+        &self,
+        runtime: &Handle,
+        play: Play,
+        transition: Transition,
+        volume: i64,
+    ) -> Result<(), PlayerError> {
+        self.send(Update::new(self.guild_id, None, None, 0, None))?;
+        self.send(play)?;
+        self.spawn_ramp(
+            runtime,
+            transition,
+            vec![Ramp {
+                player_id: None,
+                from: 0,
+                to: volume,
+            }],
+            |_| Ok(()),
+        );
+
+        Ok(())
+    }
+
+    /// Overlap the current track and a new track on mixer players, then play
+    /// the new track on the player once the current track faded out.
+    ///
+    /// The player is paused meanwhile, because it isn't heard while the mixer
+    /// is enabled.
+    fn crossfade(
+        &self,
+        runtime: &Handle,
+        current: CurrentTrack,
+        play: Play,
+        transition: Transition,
+        volume: i64,
+    ) -> Result<(), PlayerError> {
+        let guild_id = self.guild_id;
+        let position = self.estimated_position().map(|position| position as u64);
+
+        tracing::debug!("crossfading guild player {}", guild_id);
+
+        let mut incoming = play.clone().mixer_player(CROSSFADE_INCOMING);
+        incoming.no_replace = false;
+
+        self.send(
+            Play::new_complex(guild_id, current.track, position, None, false)
+                .mixer_player(CROSSFADE_OUTGOING),
+        )?;
+        self.send(
+            Update::new(guild_id, None, None, volume, self.filters())
+                .mixer_player(CROSSFADE_OUTGOING),
+        )?;
+        self.send(incoming)?;
+        self.send(
+            Update::new(guild_id, None, None, 0, self.filters()).mixer_player(CROSSFADE_INCOMING),
+        )?;
+        self.send(Mixer::new(guild_id, true))?;
+        self.send(Update::new(guild_id, true, None, None, None))?;
+
+        self.spawn_ramp(
+            runtime,
+            transition,
+            vec![
+                Ramp {
+                    player_id: Some(CROSSFADE_OUTGOING),
+                    from: volume,
+                    to: 0,
+                },
+                Ramp {
+                    player_id: Some(CROSSFADE_INCOMING),
+                    from: 0,
+                    to: volume,
+                },
+            ],
+            move |player| {
+                let elapsed = transition.duration.as_millis() as u64;
+                let mut play = play;
+                play.start_time = Some(play.start_time.unwrap_or(0) + elapsed);
+                play.no_replace = false;
+
+                player.send(play)?;
+                player.send(Update::new(guild_id, false, None, volume, None))?;
+                player.send(Mixer::new(guild_id, false))?;
+                player.send(Destroy::new(guild_id).mixer_player(CROSSFADE_OUTGOING))?;
+                player.send(Destroy::new(guild_id).mixer_player(CROSSFADE_INCOMING))
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Begin a new transition, cancelling the running one, and return the
+    /// volume to fade from or to.
+    ///
+    /// While a transition is running, the volume is the one from before it
+    /// began rather than the current volume of the ramp.
+    fn begin_transition(&self) -> i64 {
+        let volume = self.volume();
+        let mut transitions = self.lock_transitions();
+        transitions.generation = transitions.generation.wrapping_add(1);
+
+        *transitions.volume.get_or_insert(volume)
+    }
+
+    /// Cancel the running transition, if any.
+    fn cancel_transition(&self) {
+        let mut transitions = self.lock_transitions();
+        transitions.generation = transitions.generation.wrapping_add(1);
+        transitions.volume = None;
+    }
+
+    /// Change the volume of the player or its mixer players in steps on a
+    /// runtime, then call a function with the player unless the transition was
+    /// cancelled meanwhile.
+    fn spawn_ramp(
+        &self,
+        runtime: &Handle,
+        transition: Transition,
+        ramps: Vec<Ramp>,
+        then: impl FnOnce(&Player) -> Result<(), PlayerError> + Send + 'static,
+    ) {
+        let generation = self.lock_transitions().generation;
+        let guild_id = self.guild_id;
+        let players = self.node.players().clone();
+        let steps = transition.steps();
+
+        runtime.spawn(async move {
+            for step in 1..=steps {
+                sleep(transition.interval).await;
+
+                let player = match players.get(&guild_id) {
+                    Some(player) => player,
+                    None => return,
+                };
+
+                if player.lock_transitions().generation != generation {
+                    tracing::debug!("transition of guild player {} was cancelled", guild_id);
+
+                    return;
+                }
+
+                for ramp in &ramps {
+                    let mut update =
+                        Update::new(guild_id, None, None, ramp.volume(step, steps), None);
+
+                    if let Some(player_id) = ramp.player_id {
+                        update = update.mixer_player(player_id);
+                    }
+
+                    if let Err(source) = player.send(update) {
+                        tracing::warn!(
+                            "couldn't change volume of guild player {}: {}",
+                            guild_id,
+                            source
+                        );
+
+                        return;
+                    }
+                }
+            }
+
+            let player = match players.get(&guild_id) {
+                Some(player) => player,
+                None => return,
+            };
+
+            if player.lock_transitions().generation != generation {
+                return;
+            }
+
+            if let Err(source) = then(&player) {
+                tracing::warn!(
+                    "couldn't finish transition of guild player {}: {}",
+                    guild_id,
+                    source
+                );
+            }
+
+            let mut transitions = player.lock_transitions();
+
+            // The function may have begun another transition, which still
+            // needs the volume from before this one.
+            if transitions.generation == generation {
+                transitions.volume = None;
+            }
+        });
+    }
+
+    fn lock_transitions(&self) -> MutexGuard<'_, Transitions> {
+        self.transitions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Enable or disable the player's mixer.
+    ///
+    /// While the mixer is enabled, tracks played on mixer players are mixed
+    /// together, such as to layer sound effects over music.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn set_mixer_enabled(&self, enabled: bool) -> Result<(), PlayerError> {
+        self.send(Mixer::new(self.guild_id, enabled))
+    }
+
+    /// Play a track on a mixer player, creating it if it doesn't exist yet.
+    ///
+    /// The track is only heard while the mixer is [enabled].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::PositionOutOfRange`] if the start or end time is
+    /// past the end of the track, or if the end time is before the start time.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::PositionOutOfRange`]: enum.PlayerError.html#variant.PositionOutOfRange
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [enabled]: #method.set_mixer_enabled
+    pub fn play_on_mixer(
+        &self,
+        player_id: impl Into<String>,
+        track: impl Into<String>,
+        options: PlayOptions,
+    ) -> Result<(), PlayerError> {
+        let track = track.into();
+        validate_play(&track, &options)?;
+
+        self.send(
+            Play::new_complex(
+                self.guild_id,
+                track,
+                options.start_time,
+                options.end_time,
+                options.no_replace,
+            )
+            .mixer_player(player_id),
+        )
+    }
+
+    /// Update the pause state, position, volume, or filters of a mixer player.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::InvalidVolume`] if the volume isn't between 0
+    /// and 1000.
+    ///
+    /// Returns [`PlayerError::PositionOutOfRange`] if the position is
+    /// negative.
+    ///
+    /// Returns [`PlayerError::UnsupportedFilter`] if a filter alters the audio
+    /// but the player's node doesn't support it.
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::InvalidVolume`]: enum.PlayerError.html#variant.InvalidVolume
+    /// [`PlayerError::PositionOutOfRange`]: enum.PlayerError.html#variant.PositionOutOfRange
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    /// [`PlayerError::UnsupportedFilter`]: enum.PlayerError.html#variant.UnsupportedFilter
+    pub fn update_mixer_player(
+        &self,
+        player_id: impl Into<String>,
+        pause: impl Into<Option<bool>>,
+        position: impl Into<Option<i64>>,
+        volume: impl Into<Option<i64>>,
+        filters: impl Into<Option<Filters>>,
+    ) -> Result<(), PlayerError> {
+        let update = Update::new(self.guild_id, pause, position, volume, filters);
+
+        if let Some(volume) = update.volume {
+            if !(0..=MAX_VOLUME).contains(&volume) {
+                return Err(PlayerError::InvalidVolume { volume });
+            }
+        }
+
+        if let Some(position) = update.position.filter(|position| *position < 0) {
+            return Err(PlayerError::PositionOutOfRange {
+                position,
+                length: None,
+            });
+        }
+
+        self.send(update.mixer_player(player_id))
+    }
+
+    /// Stop playing the track of a mixer player.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn stop_mixer_player(&self, player_id: impl Into<String>) -> Result<(), PlayerError> {
+        self.send(Stop::new(self.guild_id).mixer_player(player_id))
+    }
+
+    /// Remove a mixer player from the mixer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn remove_mixer_player(&self, player_id: impl Into<String>) -> Result<(), PlayerError> {
+        self.send(Destroy::new(self.guild_id).mixer_player(player_id))
+    }
+
+    /// Return a copy of the player's queue.
+    pub fn queue(&self) -> Queue {
+        self.lock_queue().clone()
+    }
+
+    /// Add a track to the end of the player's queue.
+    ///
+    /// If the player isn't playing a track, the track is played right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the track had to be played right
+    /// away and the node has been removed.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn enqueue(&self, track: QueuedTrack) -> Result<(), PlayerError> {
+        let index = self.lock_queue().push(track.clone());
+        self.emit(QueueEvent::Added {
+            guild_id: self.guild_id,
+            track,
+            index,
+        });
+
+        if self.current_track().is_none() {
+            self.advance(Advance::Skipped)?;
+        }
+
+        Ok(())
+    }
+
+    /// Skip the current track and play the next track of the player's queue.
+    ///
+    /// The player is stopped if the queue has no track left to play. Returns
+    /// the track that is now playing, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the node couldn't accept the event.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn skip(&self) -> Result<Option<QueuedTrack>, PlayerError> {
+        let track = self.advance(Advance::Skipped)?;
+
+        if track.is_none() {
+            self.stop()?;
+        }
+
+        Ok(track)
+    }
+
+    /// Remove the upcoming track at an index from the player's queue.
+    pub fn remove_queued(&self, index: usize) -> Option<QueuedTrack> {
+        let track = self.lock_queue().remove(index)?;
+        self.emit(QueueEvent::Removed {
+            guild_id: self.guild_id,
+            track: track.clone(),
+            index,
+        });
+
+        Some(track)
+    }
+
+    /// Move the upcoming track at an index of the player's queue to another
+    /// index.
+    ///
+    /// Returns whether both indexes are in bounds.
+    pub fn move_queued(&self, from: usize, to: usize) -> bool {
+        if !self.lock_queue().move_track(from, to) {
+            return false;
+        }
+
+        self.emit(QueueEvent::Moved {
+            guild_id: self.guild_id,
+            from,
+            to,
+        });
+
+        true
+    }
+
+    /// Shuffle the upcoming tracks of the player's queue.
+    pub fn shuffle_queue(&self) {
+        self.lock_queue().shuffle();
+        self.emit(QueueEvent::Shuffled {
+            guild_id: self.guild_id,
+        });
+    }
+
+    /// Remove all upcoming tracks from the player's queue.
+    pub fn clear_queue(&self) {
+        self.lock_queue().clear();
+        self.emit(QueueEvent::Cleared {
+            guild_id: self.guild_id,
+        });
+    }
+
+    /// Set the repeat mode of the player's queue.
+    pub fn set_repeat(&self, repeat: RepeatMode) {
+        self.lock_queue().set_repeat(repeat);
+        self.emit(QueueEvent::RepeatChanged {
+            guild_id: self.guild_id,
+            repeat,
+        });
+    }
+
+    /// Play the next track of the queue after a track ended.
+    ///
+    /// Tracks that weren't played from the queue don't affect which track is
+    /// played next.
+    pub(crate) fn advance_after(&self, track: &str, failed: bool) -> Result<(), PlayerError> {
+        {
+            let mut queue = self.lock_queue();
+
+            if queue.current().is_none() && queue.is_empty() {
+                return Ok(());
+            }
+
+            if queue
+                .current()
+                .is_some_and(|current| current.track != track)
+            {
+                queue.forget_current();
+            }
+        }
+
+        let advance = if failed {
+            Advance::Failed
+        } else {
+            Advance::Finished
+        };
+
+        self.advance(advance).map(|_| ())
+    }
+
+    /// Whether a track was played from the queue and the queue has a track to
+    /// play after it.
+    fn has_next_queued(&self, track: &str) -> bool {
+        let queue = self.lock_queue();
+
+        queue
+            .current()
+            .is_some_and(|current| current.track == track)
+            && (!queue.is_empty() || queue.repeat() != RepeatMode::Off)
+    }
+
+    /// Advance the queue and play its next track, if any.
+    fn advance(&self, advance: Advance) -> Result<Option<QueuedTrack>, PlayerError> {
+        let next = self.lock_queue().advance(advance);
+
+        match next.as_ref() {
+            Some(track) => {
+                self.play(track.track.as_str())?;
+                self.emit(QueueEvent::Advanced {
+                    guild_id: self.guild_id,
+                    track: track.clone(),
+                });
+            }
+            None => self.emit(QueueEvent::Finished {
+                guild_id: self.guild_id,
+            }),
+        }
+
+        Ok(next)
+    }
+
+    fn lock_queue(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn emit(&self, event: QueueEvent) {
+        self.subscribers.emit(event);
+    }
+
+    /// Move the player to another node, preserving its playback state.
+    ///
+    /// The player is destroyed on its current node, and its voice connection
+    /// is forwarded to the new node. The track that it was playing, if any,
+    /// is restarted at its estimated position with the same pause state,
+    /// volume, and filters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlayerError::Sending`] if the new node couldn't accept the
+    /// events. The player is linked to the new node either way.
+    ///
+    /// [`PlayerError::Sending`]: enum.PlayerError.html#variant.Sending
+    pub fn move_to(&mut self, node: Node) -> Result<(), PlayerError> {
+        if self.node.config().address == node.config().address {
+            return Ok(());
+        }
+
+        tracing::debug!(
+            "moving guild player {} from {} to {}",
+            self.guild_id,
+            self.node.config().address,
+            node.config().address
+        );
+
+        // The current node may have disconnected, in which case there is no
+        // player left to destroy.
+        let _ = self.node.send(Destroy::new(self.guild_id));
+        self.node = node;
+        self.cancel_transition();
+
+        self.reconnect()
+    }
+
+    /// Forward the last voice update to the player's node again, and restart
+    /// the track that it was playing, if any, at its estimated position with
+    /// the same pause state, volume, and filters.
+    pub(crate) fn reconnect(&self) -> Result<(), PlayerError> {
+        if let Some(update) = self.voice_update.clone() {
+            self._send(update.into())?;
+        }
+
+        self.restore()
+    }
+
+    /// Restart the track that the player was playing, if any, at its
+    /// estimated position with the same end time, pause state, volume, and
+    /// filters.
+    ///
+    /// The mixer is enabled again if it was enabled, but the tracks of its
+    /// mixer players aren't restarted.
+    pub(crate) fn restore(&self) -> Result<(), PlayerError> {
+        if self.mixer_enabled {
+            self._send(Mixer::new(self.guild_id, true).into())?;
+        }
+
+        if let Some(CurrentTrack { track, .. }) = self.current_track() {
+            let start_time = self.estimated_position().map(|position| position as u64);
+            let end_time = self.lock_schedule().end_time;
+            let update = Update::new(
+                self.guild_id,
+                self.paused(),
+                None,
+                self.volume(),
+                self.filters(),
+            );
+
+            self._send(
+                Play::new_complex(self.guild_id, track, start_time, end_time, false).into(),
+            )?;
+            self._send(update.into())?;
+        }
+
+        Ok(())
+    }
+
+    /// Return an immutable reference to the node linked to the player.
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Return a copy of the player's guild ID.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+
+    /// Return a copy of the Unix timestamp in milliseconds of the node when it
+    /// last reported the player's state.
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// Return a mutable reference to the player's time.
+    pub(crate) fn time_mut(&mut self) -> &mut i64 {
+        &mut self.time
+    }
+
+    /// Return a mutable reference to when the node's report of the player's
+    /// state was received.
+    pub(crate) fn updated_at_mut(&mut self) -> &mut Option<Instant> {
+        &mut self.updated_at
+    }
+
+    /// Estimate the current position of the player's track in milliseconds.
+    ///
+    /// The position is extrapolated from the position that the node last
+    /// reported, or the position that was last seeked to, using the time that
+    /// has passed since. Time doesn't pass while the player isn't playing, and
+    /// passes faster or slower according to the speed and rate of its
+    /// [`Timescale`] filter. The position is clamped to the length of the
+    /// track if it's known.
+    ///
+    /// The node's clock is used to determine when it reported the position,
+    /// unless it's too far off from the local clock.
+    ///
+    /// [`Timescale`]: ../model/outgoing/struct.Timescale.html
+    pub fn estimated_position(&self) -> Option<i64> {
+        let (position, elapsed) = {
+            let pending = self.lock_pending();
+
+            match (
+                &pending.state.track,
+                pending.state.position,
+                pending.sent_at,
+            ) {
+                (Some(None), _, _) => return None,
+                (_, Some(position), Some(sent_at)) => (position, sent_at.elapsed()),
+                _ => (self.position?, self.elapsed_since_update()),
+            }
+        };
+
+        let mut position = position;
+
+        if self.status() == PlayerStatus::Playing {
+            let factor = self
+                .filters()
+                .timescale
+                .map(|timescale| timescale.speed * timescale.rate)
+                .filter(|factor| *factor > 0f64)
+                .unwrap_or(1f64);
+
+            position += (elapsed.as_millis() as f64 * factor) as i64;
+        }
+
+        if let Some(length) = self.current_track().and_then(|track| track.length()) {
+            position = position.min(length as i64);
+        }
+
+        Some(position.max(0))
+    }
+
+    /// Determine how much time has passed since the node reported the
+    /// player's state.
+    fn elapsed_since_update(&self) -> Duration {
+        let local = self
+            .updated_at
+            .map_or(Duration::from_secs(0), |updated_at| updated_at.elapsed());
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_millis() as i64);
+        let server = Duration::from_millis((now - self.time).max(0) as u64);
+
+        if server >= local && server <= local + MAX_LATENCY {
+            server
+        } else {
+            local
+        }
+    }
+
+    /// Return a copy of the player's position as last reported by the node or
+    /// seeked to, including pending changes.
+    ///
+    /// Use [`estimated_position`] to retrieve the current position.
+    ///
+    /// [`estimated_position`]: #method.estimated_position
+    pub fn position(&self) -> Option<i64> {
+        let pending = self.lock_pending();
+
+        match pending.state.track {
+            Some(None) => None,
+            _ => pending.state.position.or(self.position),
+        }
+    }
+
+    /// Return a mutable reference to the player's position.
+    pub(crate) fn position_mut(&mut self) -> &mut Option<i64> {
+        &mut self.position
+    }
+
+    /// Return a copy of whether the player is paused, including pending
+    /// changes.
+    pub fn paused(&self) -> bool {
+        self.lock_pending().state.paused.unwrap_or(self.paused)
+    }
+
+    /// Return a mutable copy of whether the player is paused.
+    pub(crate) fn paused_mut(&mut self) -> &mut bool {
+        &mut self.paused
+    }
+
+    /// Return a copy of the player's volume, including pending changes.
+    pub fn volume(&self) -> i64 {
+        self.lock_pending().state.volume.unwrap_or(self.volume)
+    }
+
+    /// Return a mutable reference to the player's volume.
+    pub(crate) fn volume_mut(&mut self) -> &mut i64 {
+        &mut self.volume
+    }
+
+    /// Return a copy of the player's filters, including pending changes.
+    pub fn filters(&self) -> Filters {
+        self.lock_pending()
+            .state
+            .filters
+            .clone()
+            .unwrap_or_else(|| self.filters.clone())
+    }
+
+    /// Return a mutable copy of the player's filters.
+    pub(crate) fn filters_mut(&mut self) -> &mut Filters {
+        &mut self.filters
+    }
+
+    /// Return a copy of the track that the player is playing, if any,
+    /// including pending changes.
+    pub fn current_track(&self) -> Option<CurrentTrack> {
+        match self.lock_pending().state.track.clone() {
+            Some(track) => track,
+            None => self.current_track.clone(),
+        }
+    }
+
+    /// Return a mutable reference to the track that the player is playing.
+    pub(crate) fn current_track_mut(&mut self) -> &mut Option<CurrentTrack> {
+        &mut self.current_track
+    }
+
+    /// Return a copy of the player's playback status, including pending
+    /// changes.
+    pub fn status(&self) -> PlayerStatus {
+        let track = self.lock_pending().state.track.clone();
+
+        match (track, self.status) {
+            (Some(None), _) => PlayerStatus::Idle,
+            (Some(Some(_)), _) | (None, PlayerStatus::Playing) | (None, PlayerStatus::Paused) => {
+                if self.paused() {
+                    PlayerStatus::Paused
+                } else {
+                    PlayerStatus::Playing
+                }
+            }
+            (None, status) => status,
+        }
+    }
+
+    /// Return a mutable reference to the player's playback status.
+    pub(crate) fn status_mut(&mut self) -> &mut PlayerStatus {
+        &mut self.status
+    }
+
+    /// Return an immutable reference to the voice update that was last
+    /// forwarded to the player's node, if any.
+    pub fn voice_update(&self) -> Option<&VoiceUpdate> {
+        self.voice_update.as_ref()
+    }
+
+    /// Return a mutable reference to the voice update that was last forwarded
+    /// to the player's node.
+    pub(crate) fn voice_update_mut(&mut self) -> &mut Option<VoiceUpdate> {
+        &mut self.voice_update
+    }
+
+    /// Return a copy of whether the player's mixer is enabled, as last
+    /// reported by the node.
+    pub fn mixer_enabled(&self) -> bool {
+        self.mixer_enabled
+    }
+
+    /// Return a mutable reference to whether the player's mixer is enabled.
+    pub(crate) fn mixer_enabled_mut(&mut self) -> &mut bool {
+        &mut self.mixer_enabled
+    }
+
+    /// Return an immutable reference to the state of each of the player's
+    /// mixer players as last reported by the node, keyed by their IDs.
+    pub fn mixer_players(&self) -> &HashMap<String, MixerPlayerState> {
+        &self.mixer
+    }
+
+    /// Return a mutable reference to the state of the player's mixer players.
+    pub(crate) fn mixer_players_mut(&mut self) -> &mut HashMap<String, MixerPlayerState> {
+        &mut self.mixer
+    }
+}
+
+/// Retrieve the handle of the Tokio runtime that the player is controlled
+/// from, which runs its transitions.
+fn runtime() -> Result<Handle, PlayerError> {
+    Handle::try_current().map_err(|_| PlayerError::NoRuntime)
+}
+
+/// Validate the start and end time of a track to play.
+fn validate_play(track: &str, options: &PlayOptions) -> Result<(), PlayerError> {
+    let length = CurrentTrack::new(track).length();
+
+    for time in options.start_time.iter().chain(options.end_time.iter()) {
+        if length.is_some_and(|length| *time > length) {
+            return Err(PlayerError::PositionOutOfRange {
+                position: *time as i64,
+                length,
+            });
+        }
+    }
+
+    if let (Some(start_time), Some(end_time)) = (options.start_time, options.end_time) {
+        if end_time < start_time {
+            return Err(PlayerError::PositionOutOfRange {
+                position: end_time as i64,
+                length,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurrentTrack, Player, PlayerError, PlayerManager, PlayerStatus, PENDING_TIMEOUT};
+    #[cfg(feature = "http")]
+    use super::{PlayOptions, Transition};
+    #[cfg(feature = "http")]
+    use crate::{
+        http::TrackInfo,
+        model::{Opcode, TrackEnd, TrackEndReason, TrackEventType},
+        queue::QueuedTrack,
+    };
+    use crate::{
+        model::{FilterKind, Filters, Rotation, Timescale},
+        node::{Node, NodeConfig},
+    };
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use tokio::net::TcpListener;
+    use twilight_model::id::{GuildId, UserId};
+
+    const GUILD_ID: GuildId = GuildId(1);
+
+    /// Create a node that never connects, whose events are buffered.
+    async fn node() -> Node {
+        // Nothing listens on the port once the listener is dropped.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let config = NodeConfig::new(UserId(2), address, "", None);
+        let (node, _) = Node::connect(config, PlayerManager::new()).await.unwrap();
+
+        node
+    }
+
+    async fn player() -> Player {
+        Player::new(GUILD_ID, node().await, Default::default())
+    }
+
+    /// Make a player appear to have been reported at a position a while ago.
+    fn updated_ago(player: &mut Player, position: i64, ago: Duration) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+        *player.position_mut() = Some(position);
+        *player.updated_at_mut() = Instant::now().checked_sub(ago);
+        *player.time_mut() = (now - ago).as_millis() as i64;
+    }
+
+    /// Make the pending changes of a player appear to have been sent a while
+    /// ago.
+    fn sent_ago(player: &Player, ago: Duration) {
+        player.lock_pending().sent_at = Instant::now().checked_sub(ago);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_confirmed() {
+        let mut player = player().await;
+
+        {
+            let mut pending = player.lock_pending();
+            pending.state.paused = Some(true);
+            pending.state.volume = Some(50);
+            pending.sent_at = Some(Instant::now());
+        }
+
+        *player.paused_mut() = true;
+        player.reconcile();
+        assert_eq!(Some(50), player.pending().volume);
+        assert!(player.pending().paused.is_none());
+
+        *player.volume_mut() = 50;
+        player.reconcile();
+        assert!(player.pending().is_empty());
+        assert!(player.lock_pending().sent_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_position_tolerance() {
+        let mut player = player().await;
+
+        {
+            let mut pending = player.lock_pending();
+            pending.state.position = Some(10_000);
+        }
+        sent_ago(&player, Duration::from_secs(2));
+
+        // A position from before the seek isn't confirmation.
+        *player.position_mut() = Some(3_000);
+        player.reconcile();
+        assert_eq!(Some(10_000), player.pending().position);
+        assert_eq!(Some(10_000), player.position());
+
+        // The node may report a position slightly before the one sought to,
+        // or one that advanced since the seek was sent.
+        *player.position_mut() = Some(9_500);
+        player.reconcile();
+        assert!(player.pending().position.is_none());
+
+        player.lock_pending().state.position = Some(10_000);
+        sent_ago(&player, Duration::from_secs(2));
+        *player.position_mut() = Some(12_500);
+        player.reconcile();
+        assert!(player.pending().position.is_none());
+
+        // But not one that advanced further than the time since.
+        player.lock_pending().state.position = Some(10_000);
+        sent_ago(&player, Duration::from_secs(2));
+        *player.position_mut() = Some(14_000);
+        player.reconcile();
+        assert_eq!(Some(10_000), player.pending().position);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_timed_out() {
+        let mut player = player().await;
+        let track = CurrentTrack::new("track");
+
+        {
+            let mut pending = player.lock_pending();
+            pending.state.track = Some(Some(track.clone()));
+            pending.state.position = Some(0);
+            pending.state.volume = Some(50);
+        }
+        sent_ago(&player, PENDING_TIMEOUT + Duration::from_secs(1));
+
+        // The track is still loading, so only the other changes time out.
+        player.reconcile();
+        assert_eq!(Some(Some(track.clone())), player.pending().track);
+        assert!(player.pending().position.is_none());
+        assert!(player.pending().volume.is_none());
+        assert_eq!(Some(track.clone()), player.current_track());
+
+        player.reconcile();
+        assert_eq!(Some(track.clone()), player.current_track());
+
+        *player.current_track_mut() = Some(track.clone());
+        player.confirm_track();
+        assert!(player.pending().is_empty());
+        assert_eq!(Some(track), player.current_track());
+    }
+
+    #[tokio::test]
+    async fn test_fail_track() {
+        let mut player = player().await;
+        player.lock_pending().state.track = Some(Some(CurrentTrack::new("track")));
+
+        player.fail_track("other");
+        assert!(player.current_track().is_some());
+
+        player.fail_track("track");
+        assert!(player.pending().is_empty());
+        assert!(player.current_track().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_stopped_idle() {
+        let mut player = player().await;
+        player.lock_pending().state.track = Some(None);
+
+        player.reconcile();
+        assert!(player.pending().is_empty());
+    }
+
+    #[cfg(feature = "http")]
+    fn track_info(identifier: &str, length: u64) -> TrackInfo {
+        TrackInfo {
+            class: "com.sedmelluq.discord.lavaplayer.source.youtube.YoutubeAudioTrack".to_owned(),
+            title: identifier.to_owned(),
+            author: String::new(),
+            length,
+            identifier: identifier.to_owned(),
+            uri: String::new(),
+            is_stream: false,
+            is_seekable: true,
+            position: 0,
+        }
+    }
+
+    /// Encode a track with a length.
+    #[cfg(feature = "http")]
+    fn track(identifier: &str, length: u64) -> String {
+        crate::track::encode(track_info(identifier, length))
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_transition_before_end() {
+        let node = node().await;
+        let first = track("first", 10_000);
+        let second = track("second", 10_000);
+
+        {
+            let mut player = node.players().get_or_insert(GUILD_ID, node.clone());
+            player.set_transition(
+                Transition::new(Duration::from_millis(200)).interval(Duration::from_millis(50)),
+            );
+            player.enqueue(QueuedTrack::new(first.as_str())).unwrap();
+            player.enqueue(QueuedTrack::new(second.as_str())).unwrap();
+
+            // The node started the first track and reported it to be 300
+            // milliseconds before its end.
+            *player.lock_pending() = Default::default();
+            *player.current_track_mut() = Some(CurrentTrack::new(first.as_str()));
+            *player.status_mut() = PlayerStatus::Playing;
+            updated_ago(&mut player, 9_700, Duration::from_secs(0));
+        }
+
+        let current = |node: &Node| {
+            let player = node.players().get(&GUILD_ID).unwrap();
+
+            (
+                player.queue().current().map(|track| track.track.clone()),
+                player.current_track().map(|track| track.track),
+            )
+        };
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!((Some(first.clone()), Some(first.clone())), current(&node));
+
+        // The queue advances 200 milliseconds before the end, and the first
+        // track fades out meanwhile.
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!((Some(second.clone()), Some(first.clone())), current(&node));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!((Some(second.clone()), Some(second.clone())), current(&node));
+
+        // The end of the first track doesn't advance the queue again.
+        node.end_track(TrackEnd {
+            op: Opcode::Event,
+            kind: TrackEventType::End,
+            guild_id: GUILD_ID,
+            user_id: None,
+            track: first,
+            reason: TrackEndReason::Finished,
+        });
+        assert_eq!((Some(second.clone()), Some(second)), current(&node));
+    }
+
+    #[tokio::test]
+    async fn test_set_volume_out_of_range() {
+        let player = player().await;
+
+        assert!(matches!(
+            player.set_volume(-1),
+            Err(PlayerError::InvalidVolume { volume: -1 })
+        ));
+        assert!(matches!(
+            player.set_volume(1001),
+            Err(PlayerError::InvalidVolume { volume: 1001 })
+        ));
+        assert!(player.pending().volume.is_none());
+
+        assert!(player.set_volume(0).is_ok());
+        assert!(player.set_volume(1000).is_ok());
+        assert_eq!(Some(1000), player.pending().volume);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_seek_out_of_range() {
+        let player = player().await;
+        assert!(matches!(player.seek(0), Err(PlayerError::NothingPlaying)));
+
+        player.lock_pending().state.track = Some(Some(CurrentTrack::new(track("track", 10_000))));
+
+        assert!(matches!(
+            player.seek(-1),
+            Err(PlayerError::PositionOutOfRange {
+                position: -1,
+                length: Some(10_000),
+            })
+        ));
+        assert!(matches!(
+            player.seek(10_001),
+            Err(PlayerError::PositionOutOfRange {
+                position: 10_001,
+                length: Some(10_000),
+            })
+        ));
+
+        assert!(player.seek(10_000).is_ok());
+        assert_eq!(Some(10_000), player.pending().position);
+
+        let mut stream = track_info("stream", 0);
+        stream.is_stream = true;
+        player.lock_pending().state.track =
+            Some(Some(CurrentTrack::new(crate::track::encode(stream))));
+
+        assert!(matches!(player.seek(0), Err(PlayerError::Unseekable)));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_play_with_out_of_range() {
+        let player = player().await;
+        let track = track("track", 10_000);
+
+        assert!(matches!(
+            player.play_with(track.as_str(), PlayOptions::new().start_time(10_001)),
+            Err(PlayerError::PositionOutOfRange {
+                position: 10_001,
+                length: Some(10_000),
+            })
+        ));
+        assert!(matches!(
+            player.play_with(track.as_str(), PlayOptions::new().end_time(10_001)),
+            Err(PlayerError::PositionOutOfRange {
+                position: 10_001,
+                length: Some(10_000),
+            })
+        ));
+        assert!(matches!(
+            player.play_with(
+                track.as_str(),
+                PlayOptions::new().start_time(2_000).end_time(1_000)
+            ),
+            Err(PlayerError::PositionOutOfRange {
+                position: 1_000,
+                length: Some(10_000),
+            })
+        ));
+        assert!(player.current_track().is_none());
+
+        assert!(player
+            .play_with(
+                track.as_str(),
+                PlayOptions::new().start_time(1_000).end_time(10_000)
+            )
+            .is_ok());
+        assert_eq!(Some(1_000), player.pending().position);
+    }
+
+    #[tokio::test]
+    async fn test_estimated_position_paused() {
+        let mut player = player().await;
+        assert!(player.estimated_position().is_none());
+
+        *player.current_track_mut() = Some(CurrentTrack::new("track"));
+        *player.status_mut() = PlayerStatus::Paused;
+        *player.paused_mut() = true;
+        updated_ago(&mut player, 5_000, Duration::from_secs(2));
+
+        assert_eq!(Some(5_000), player.estimated_position());
+    }
+
+    #[tokio::test]
+    async fn test_estimated_position_playing() {
+        let mut player = player().await;
+        *player.current_track_mut() = Some(CurrentTrack::new("track"));
+        *player.status_mut() = PlayerStatus::Playing;
+        updated_ago(&mut player, 5_000, Duration::from_secs(2));
+
+        let position = player.estimated_position().unwrap();
+        assert!((7_000..7_100).contains(&position), "{}", position);
+
+        // Time passes faster with the speed and rate of the timescale.
+        player.filters_mut().timescale = Some(Timescale::new(1.5, 1.0, 2.0));
+
+        let position = player.estimated_position().unwrap();
+        assert!((11_000..11_300).contains(&position), "{}", position);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_estimated_position_clamped() {
+        let mut player = player().await;
+        *player.current_track_mut() = Some(CurrentTrack::new(track("track", 6_000)));
+        *player.status_mut() = PlayerStatus::Playing;
+        updated_ago(&mut player, 5_000, Duration::from_secs(2));
+
+        assert_eq!(Some(6_000), player.estimated_position());
+    }
+
+    #[tokio::test]
+    async fn test_set_filters_unsupported() {
+        let player = player().await;
+
+        assert!(matches!(
+            player.set_filters(Filters::default().rotation(Rotation::new(0.2))),
+            Err(PlayerError::UnsupportedFilter {
+                filter: FilterKind::Rotation,
+            })
+        ));
+        assert!(player.pending().filters.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_filters_reset() {
+        let player = player().await;
+
+        // Andesite doesn't support every filter, but their defaults don't
+        // alter the audio, so they are left out.
+        assert!(player.set_filters(Filters::reset()).is_ok());
+
+        let filters = player.pending().filters.unwrap();
+        assert_eq!(FilterKind::ANDESITE, filters.kinds().as_slice());
+        assert_eq!(Filters::reset().karaoke, filters.karaoke);
+        assert!(filters.rotation.is_none());
+        assert!(filters.low_pass.is_none());
     }
 }
@@ -0,0 +1,360 @@
+//! Queues of tracks that players advance through automatically.
+//!
+//! Every [`Player`] has a [`Queue`], which stays empty until tracks are
+//! [enqueued]. When a track finishes playing or fails to load, the player
+//! plays the next track of its queue according to the queue's
+//! [`RepeatMode`]. Changes to queues are emitted as [`QueueEvent`]s via
+//! [`Lavalink::events`].
+//!
+//! [`Lavalink::events`]: ../client/struct.Lavalink.html#method.events
+//! [`Player`]: ../player/struct.Player.html
+//! [`Queue`]: struct.Queue.html
+//! [`QueueEvent`]: enum.QueueEvent.html
+//! [`RepeatMode`]: enum.RepeatMode.html
+//! [enqueued]: ../player/struct.Player.html#method.enqueue
+
+#[cfg(feature = "http")]
+use crate::http::TrackInfo;
+use crate::random;
+use std::collections::VecDeque;
+use twilight_model::id::{GuildId, UserId};
+
+/// A track in a queue, along with who requested it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedTrack {
+    /// The base64 track.
+    pub track: String,
+    /// The decoded information of the track, if it could be decoded.
+    #[cfg(feature = "http")]
+    pub info: Option<TrackInfo>,
+    /// The ID of the user that requested the track, if any.
+    pub requester: Option<UserId>,
+}
+
+impl QueuedTrack {
+    /// Create a new queued track, decoding its information.
+    pub fn new(track: impl Into<String>) -> Self {
+        Self::new_with_requester(track, None)
+    }
+
+    /// Similar to [`new`], but allows you to specify who requested the track.
+    ///
+    /// [`new`]: #method.new
+    pub fn new_with_requester(
+        track: impl Into<String>,
+        requester: impl Into<Option<UserId>>,
+    ) -> Self {
+        let track = track.into();
+        #[cfg(feature = "http")]
+        let info = crate::track::decode(&track).ok();
+
+        Self {
+            track,
+            #[cfg(feature = "http")]
+            info,
+            requester: requester.into(),
+        }
+    }
+}
+
+/// Which tracks of a queue are played again after they end.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RepeatMode {
+    /// Tracks are removed from the queue once they end.
+    #[default]
+    Off,
+    /// The current track is played again once it ends.
+    Track,
+    /// Tracks are added to the end of the queue once they end.
+    Queue,
+}
+
+/// The reason that a queue advances to its next track.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Advance {
+    /// The current track finished playing.
+    Finished,
+    /// The current track failed to load, so it's never repeated.
+    Failed,
+    /// The current track was skipped, so it's only repeated with the rest of
+    /// the queue.
+    Skipped,
+}
+
+/// An ordered list of tracks to play.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Queue {
+    current: Option<QueuedTrack>,
+    tracks: VecDeque<QueuedTrack>,
+    repeat: RepeatMode,
+}
+
+impl Queue {
+    /// Create a new empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return an immutable reference to the track from the queue that is
+    /// playing, if any.
+    pub fn current(&self) -> Option<&QueuedTrack> {
+        self.current.as_ref()
+    }
+
+    /// Return an immutable reference to the upcoming tracks.
+    pub fn tracks(&self) -> &VecDeque<QueuedTrack> {
+        &self.tracks
+    }
+
+    /// Return the number of upcoming tracks.
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Return whether there are no upcoming tracks.
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Return a copy of the repeat mode of the queue.
+    pub fn repeat(&self) -> RepeatMode {
+        self.repeat
+    }
+
+    /// Set the repeat mode of the queue.
+    pub fn set_repeat(&mut self, repeat: RepeatMode) {
+        self.repeat = repeat;
+    }
+
+    /// Add a track to the end of the queue, returning its index.
+    pub fn push(&mut self, track: QueuedTrack) -> usize {
+        self.tracks.push_back(track);
+
+        self.tracks.len() - 1
+    }
+
+    /// Remove the upcoming track at an index.
+    pub fn remove(&mut self, index: usize) -> Option<QueuedTrack> {
+        self.tracks.remove(index)
+    }
+
+    /// Move the upcoming track at an index to another index.
+    ///
+    /// Returns whether both indexes are in bounds.
+    pub fn move_track(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.tracks.len() || to >= self.tracks.len() {
+            return false;
+        }
+
+        if let Some(track) = self.tracks.remove(from) {
+            self.tracks.insert(to, track);
+        }
+
+        true
+    }
+
+    /// Shuffle the upcoming tracks.
+    pub fn shuffle(&mut self) {
+        for index in (1..self.tracks.len()).rev() {
+            let other = random::below(index as u64 + 1) as usize;
+
+            self.tracks.swap(index, other);
+        }
+    }
+
+    /// Remove all upcoming tracks.
+    pub fn clear(&mut self) {
+        self.tracks.clear();
+    }
+
+    /// Forget the current track, such as when a track that isn't from the
+    /// queue was played over it.
+    pub(crate) fn forget_current(&mut self) {
+        self.current = None;
+    }
+
+    /// Advance to the next track according to the repeat mode, returning it.
+    pub(crate) fn advance(&mut self, advance: Advance) -> Option<QueuedTrack> {
+        if let Some(previous) = self.current.take() {
+            match (advance, self.repeat) {
+                (Advance::Finished, RepeatMode::Track) => {
+                    self.current = Some(previous);
+
+                    return self.current.clone();
+                }
+                (Advance::Finished, RepeatMode::Queue) | (Advance::Skipped, RepeatMode::Queue) => {
+                    self.tracks.push_back(previous);
+                }
+                _ => {}
+            }
+        }
+
+        self.current = self.tracks.pop_front();
+
+        self.current.clone()
+    }
+}
+
+/// A change to the queue of a player.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QueueEvent {
+    /// A track was added to the queue.
+    Added {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+        /// The track that was added.
+        track: QueuedTrack,
+        /// The index of the track in the queue.
+        index: usize,
+    },
+    /// A track was removed from the queue.
+    Removed {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+        /// The track that was removed.
+        track: QueuedTrack,
+        /// The index that the track had in the queue.
+        index: usize,
+    },
+    /// A track was moved within the queue.
+    Moved {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+        /// The index that the track had.
+        from: usize,
+        /// The index that the track has now.
+        to: usize,
+    },
+    /// The queue was shuffled.
+    Shuffled {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+    },
+    /// The queue was cleared.
+    Cleared {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+    },
+    /// The repeat mode of the queue changed.
+    RepeatChanged {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+        /// The new repeat mode.
+        repeat: RepeatMode,
+    },
+    /// The player started playing the next track of the queue.
+    Advanced {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+        /// The track that is now playing.
+        track: QueuedTrack,
+    },
+    /// The player has no track left to play.
+    Finished {
+        /// The guild ID of the player.
+        guild_id: GuildId,
+    },
+}
+
+impl QueueEvent {
+    /// Get the guild ID of the player.
+    pub fn guild_id(&self) -> GuildId {
+        match self {
+            Self::Added { guild_id, .. }
+            | Self::Removed { guild_id, .. }
+            | Self::Moved { guild_id, .. }
+            | Self::Shuffled { guild_id }
+            | Self::Cleared { guild_id }
+            | Self::RepeatChanged { guild_id, .. }
+            | Self::Advanced { guild_id, .. }
+            | Self::Finished { guild_id } => *guild_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Advance, Queue, QueuedTrack, RepeatMode};
+
+    fn queue(tracks: &[&str]) -> Queue {
+        let mut queue = Queue::new();
+
+        for track in tracks {
+            queue.push(QueuedTrack::new(*track));
+        }
+
+        queue
+    }
+
+    fn tracks(queue: &Queue) -> Vec<&str> {
+        queue
+            .tracks()
+            .iter()
+            .map(|track| track.track.as_str())
+            .collect()
+    }
+
+    fn advance(queue: &mut Queue, advance: Advance) -> Option<String> {
+        queue.advance(advance).map(|track| track.track)
+    }
+
+    #[test]
+    fn test_repeat_off() {
+        let mut queue = queue(&["a", "b"]);
+
+        assert_eq!(Some("a".to_owned()), advance(&mut queue, Advance::Finished));
+        assert_eq!(Some("b".to_owned()), advance(&mut queue, Advance::Skipped));
+        assert!(advance(&mut queue, Advance::Finished).is_none());
+        assert!(queue.current().is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_repeat_track() {
+        let mut queue = queue(&["a", "b"]);
+        queue.set_repeat(RepeatMode::Track);
+
+        assert_eq!(Some("a".to_owned()), advance(&mut queue, Advance::Finished));
+        assert_eq!(Some("a".to_owned()), advance(&mut queue, Advance::Finished));
+        assert_eq!(vec!["b"], tracks(&queue));
+
+        // Tracks that fail to load would fail again, so they aren't repeated.
+        assert_eq!(Some("b".to_owned()), advance(&mut queue, Advance::Failed));
+        assert!(queue.is_empty());
+
+        assert_eq!(Some("b".to_owned()), advance(&mut queue, Advance::Finished));
+        assert!(advance(&mut queue, Advance::Skipped).is_none());
+    }
+
+    #[test]
+    fn test_repeat_queue() {
+        let mut queue = queue(&["a", "b"]);
+        queue.set_repeat(RepeatMode::Queue);
+
+        assert_eq!(Some("a".to_owned()), advance(&mut queue, Advance::Finished));
+        assert_eq!(Some("b".to_owned()), advance(&mut queue, Advance::Skipped));
+        assert_eq!(vec!["a"], tracks(&queue));
+        assert_eq!(Some("a".to_owned()), advance(&mut queue, Advance::Finished));
+        assert_eq!(vec!["b"], tracks(&queue));
+
+        assert_eq!(Some("b".to_owned()), advance(&mut queue, Advance::Failed));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_move_track() {
+        let mut queue = queue(&["a", "b", "c"]);
+
+        assert!(queue.move_track(0, 2));
+        assert_eq!(vec!["b", "c", "a"], tracks(&queue));
+        assert!(queue.move_track(2, 0));
+        assert_eq!(vec!["a", "b", "c"], tracks(&queue));
+
+        assert!(!queue.move_track(3, 0));
+        assert!(!queue.move_track(0, 3));
+        assert!(!queue.move_track(usize::MAX, usize::MAX));
+        assert_eq!(vec!["a", "b", "c"], tracks(&queue));
+
+        assert!(!Queue::new().move_track(0, 0));
+    }
+}
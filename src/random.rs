@@ -0,0 +1,31 @@
+//! Random numbers that are random enough for shuffling and jitter.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// Return a random number below a bound, or 0 if the bound is 0.
+///
+/// A new hasher is seeded randomly, which is random enough for shuffling
+/// queues and jittering delays without depending on a random number
+/// generator.
+pub(crate) fn below(bound: u64) -> u64 {
+    if bound == 0 {
+        return 0;
+    }
+
+    RandomState::new().build_hasher().finish() % bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::below;
+
+    #[test]
+    fn test_below() {
+        assert_eq!(0, below(0));
+        assert_eq!(0, below(1));
+        assert!((0..100).all(|_| below(10) < 10));
+    }
+}
@@ -0,0 +1,520 @@
+//! Strategies for selecting the node that new players are created on.
+//!
+//! The [`Lavalink`] client uses a [`NodeSelector`] to determine the [best]
+//! node. By default, it uses the [`Penalty`] selector, which picks the node
+//! with the lowest [penalty score]. Other selectors can be provided via
+//! [`Lavalink::new_with_selector`] to balance players differently, such as
+//! when nodes run on heterogeneous hardware, or [`RegionAware`] to prefer
+//! nodes close to a guild's Discord voice server.
+//!
+//! [`Lavalink`]: ../client/struct.Lavalink.html
+//! [`Lavalink::new_with_selector`]: ../client/struct.Lavalink.html#method.new_with_selector
+//! [`NodeSelector`]: trait.NodeSelector.html
+//! [`Penalty`]: struct.Penalty.html
+//! [`RegionAware`]: struct.RegionAware.html
+//! [best]: ../client/struct.Lavalink.html#method.best
+//! [penalty score]: ../node/struct.Node.html#method.penalty
+
+use crate::{
+    model::Stats,
+    node::{Node, NodeAddress},
+};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A node that a new player may be created on, along with its latest stats.
+#[derive(Clone, Debug)]
+pub struct NodeCandidate {
+    /// The node.
+    pub node: Node,
+    /// The latest stats of the node.
+    pub stats: Stats,
+}
+
+/// A strategy for selecting the node that new players are created on.
+///
+/// # Examples
+///
+/// Select the node with the most free memory:
+///
+/// ```
+/// use twilight_andesite::selector::{NodeCandidate, NodeSelector};
+///
+/// #[derive(Debug)]
+/// struct MostMemory;
+///
+/// impl NodeSelector for MostMemory {
+///     fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+///         (0..candidates.len()).max_by_key(|index| candidates[*index].stats.memory.free)
+///     }
+/// }
+/// ```
+pub trait NodeSelector: Debug + Send + Sync {
+    /// Select one of the candidates, returning its index.
+    ///
+    /// Candidates are sorted by address, and only include nodes that haven't
+    /// permanently disconnected. Returning `None` means that no node is
+    /// suitable.
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize>;
+
+    /// Select one of the candidates for a guild whose Discord voice server
+    /// has the given endpoint, returning its index.
+    ///
+    /// The endpoint is `None` if the guild's voice server isn't known yet.
+    /// By default, the endpoint is ignored and [`select`] is used.
+    ///
+    /// [`select`]: #tymethod.select
+    fn select_for(&self, candidates: &[NodeCandidate], endpoint: Option<&str>) -> Option<usize> {
+        let _ = endpoint;
+
+        self.select(candidates)
+    }
+}
+
+/// Retrieve the name of the Discord voice server from its endpoint.
+///
+/// This is the first label of the hostname with its trailing number removed,
+/// returning `None` if the endpoint is empty.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_andesite::selector::voice_server;
+///
+/// assert_eq!(Some("rotterdam"), voice_server("rotterdam123.discord.media:443"));
+/// assert_eq!(Some("us-east"), voice_server("us-east42.discord.gg"));
+/// ```
+pub fn voice_server(endpoint: &str) -> Option<&str> {
+    let host = endpoint.split(':').next().unwrap_or_default();
+    let label = host.split('.').next().unwrap_or_default();
+    let name = label.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Select the node with the lowest penalty score.
+///
+/// This is the default selector. Refer to [`Node::penalty`] for how the score
+/// is calculated.
+///
+/// [`Node::penalty`]: ../node/struct.Node.html#method.penalty
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Penalty;
+
+impl NodeSelector for Penalty {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        WeightedPenalty::default().select(candidates)
+    }
+}
+
+/// Select the node with the lowest penalty score, calculated with custom
+/// weights for each part of the score.
+///
+/// The default weights result in the same score as the [`Penalty`] selector,
+/// which ignores memory usage and the load of the Lavalink process.
+///
+/// [`Penalty`]: struct.Penalty.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedPenalty {
+    /// The weight of the number of playing players.
+    pub playing_players: f64,
+    /// The weight of the load of the system.
+    pub system_load: f64,
+    /// The weight of the load of the Lavalink process.
+    pub lavalink_load: f64,
+    /// The weight of the percentage of reservable memory that is used.
+    pub memory: f64,
+    /// The weight of the number of frames that were sent late.
+    pub deficit_frames: f64,
+    /// The weight of the number of frames that were nulled.
+    pub nulled_frames: f64,
+}
+
+impl WeightedPenalty {
+    /// Calculate the penalty score of a node with the given stats.
+    pub fn penalty(&self, stats: &Stats) -> f64 {
+        let load = |load: f64| 1.05f64.powf(100f64 * load) * 10f64 - 10f64;
+        let frames =
+            |frames: i64| 1.03f64.powf(500f64 * (frames as f64 / 3000f64)) * 300f64 - 300f64;
+
+        let memory = if stats.memory.reservable == 0 {
+            0f64
+        } else {
+            stats.memory.used as f64 / stats.memory.reservable as f64 * 100f64
+        };
+
+        self.playing_players * stats.playing_players as f64
+            + self.system_load * load(stats.cpu.system_load)
+            + self.lavalink_load * load(stats.cpu.lavalink_load)
+            + self.memory * memory
+            + self.deficit_frames * frames(stats.frames.as_ref().map_or(0, |f| f.deficit))
+            + self.nulled_frames * frames(stats.frames.as_ref().map_or(0, |f| f.nulled)) * 2f64
+    }
+}
+
+impl Default for WeightedPenalty {
+    fn default() -> Self {
+        Self {
+            playing_players: 1f64,
+            system_load: 1f64,
+            lavalink_load: 0f64,
+            memory: 0f64,
+            deficit_frames: 1f64,
+            nulled_frames: 1f64,
+        }
+    }
+}
+
+impl NodeSelector for WeightedPenalty {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        min_by(candidates, |candidate| self.penalty(&candidate.stats))
+    }
+}
+
+/// Select the node with the fewest players, including players that aren't
+/// playing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LeastPlayers;
+
+impl NodeSelector for LeastPlayers {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        min_by(candidates, |candidate| candidate.stats.players as f64)
+    }
+}
+
+/// Select each node in turn.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl RoundRobin {
+    /// Create a new round-robin selector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NodeSelector for RoundRobin {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        if candidates.is_empty() {
+            return None;
+        }
+
+        Some(self.next.fetch_add(1, Ordering::Relaxed) % candidates.len())
+    }
+}
+
+/// Select the node with the fewest players relative to its configured
+/// capacity.
+///
+/// Nodes with a capacity of 0 are never selected.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WeightedCapacity {
+    /// The capacity of each node, such as its maximum number of players.
+    pub capacities: HashMap<NodeAddress, u64>,
+    /// The capacity of nodes without a configured capacity.
+    pub default_capacity: u64,
+}
+
+impl WeightedCapacity {
+    /// Create a new capacity selector, providing the capacity of nodes
+    /// without a configured capacity.
+    pub fn new(default_capacity: u64) -> Self {
+        Self {
+            capacities: HashMap::new(),
+            default_capacity,
+        }
+    }
+
+    /// Configure the capacity of a node.
+    pub fn capacity(mut self, address: impl Into<NodeAddress>, capacity: u64) -> Self {
+        self.capacities.insert(address.into(), capacity);
+
+        self
+    }
+}
+
+impl NodeSelector for WeightedCapacity {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        let usable = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, candidate)| {
+                let capacity = self
+                    .capacities
+                    .get(&candidate.node.config().address)
+                    .copied()
+                    .unwrap_or(self.default_capacity);
+
+                if capacity == 0 {
+                    None
+                } else {
+                    Some((index, candidate.stats.players as f64 / capacity as f64))
+                }
+            });
+
+        usable
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+}
+
+/// Prefer nodes tagged with the region of a guild's Discord voice server,
+/// falling back to any node.
+///
+/// The region of a voice server is looked up by its [name], such as
+/// `rotterdam`, which may be mapped to a broader region, such as `europe`.
+/// Voice servers without a mapping use their name as the region. Nodes are
+/// tagged with regions via [`NodeConfig::regions`], and regions are compared
+/// case-insensitively.
+///
+/// The inner selector is used to choose among the nodes in the region, or
+/// among all nodes if there are none.
+///
+/// # Examples
+///
+/// ```
+/// use twilight_andesite::selector::{Penalty, RegionAware};
+///
+/// let selector = RegionAware::new(Penalty)
+///     .region("amsterdam", "europe")
+///     .region("rotterdam", "europe")
+///     .region("us-east", "us");
+/// assert_eq!(Some("europe"), selector.region_of("rotterdam123.discord.media:443"));
+/// assert_eq!(Some("sydney"), selector.region_of("sydney5.discord.media:443"));
+/// ```
+///
+/// [`NodeConfig::regions`]: ../node/struct.NodeConfig.html#structfield.regions
+/// [name]: fn.voice_server.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegionAware<S> {
+    /// The selector used to choose among nodes.
+    pub inner: S,
+    /// The region of each voice server, keyed by its name.
+    pub regions: HashMap<String, String>,
+}
+
+impl<S> RegionAware<S> {
+    /// Create a new region-aware selector that uses the given selector to
+    /// choose among nodes.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Map a voice server, such as `rotterdam`, to a region.
+    pub fn region(mut self, voice_server: impl Into<String>, region: impl Into<String>) -> Self {
+        self.regions
+            .insert(voice_server.into().to_ascii_lowercase(), region.into());
+
+        self
+    }
+
+    /// Retrieve the region of the voice server with the given endpoint.
+    pub fn region_of<'a>(&'a self, endpoint: &'a str) -> Option<&'a str> {
+        let name = voice_server(endpoint)?;
+
+        Some(
+            self.regions
+                .get(&name.to_ascii_lowercase())
+                .map_or(name, String::as_str),
+        )
+    }
+}
+
+impl<S: NodeSelector> NodeSelector for RegionAware<S> {
+    fn select(&self, candidates: &[NodeCandidate]) -> Option<usize> {
+        self.inner.select(candidates)
+    }
+
+    fn select_for(&self, candidates: &[NodeCandidate], endpoint: Option<&str>) -> Option<usize> {
+        if let Some(region) = endpoint.and_then(|endpoint| self.region_of(endpoint)) {
+            let (indices, local): (Vec<_>, Vec<_>) = candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| {
+                    candidate
+                        .node
+                        .config()
+                        .regions
+                        .iter()
+                        .any(|tag| tag.eq_ignore_ascii_case(region))
+                })
+                .map(|(index, candidate)| (index, candidate.clone()))
+                .unzip();
+
+            if let Some(index) = self.inner.select_for(&local, endpoint) {
+                tracing::debug!("selected node in region {}", region);
+
+                return indices.get(index).copied();
+            }
+
+            tracing::debug!("no node available in region {}", region);
+        }
+
+        self.inner.select_for(candidates, endpoint)
+    }
+}
+
+/// Return the index of the candidate with the lowest score.
+fn min_by(candidates: &[NodeCandidate], score: impl Fn(&NodeCandidate) -> f64) -> Option<usize> {
+    candidates
+        .iter()
+        .map(score)
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LeastPlayers, NodeCandidate, NodeSelector, RegionAware, RoundRobin, WeightedCapacity,
+        WeightedPenalty,
+    };
+    use crate::{
+        model::{Opcode, Stats, StatsCpu, StatsMemory},
+        node::{Node, NodeConfig},
+        player::PlayerManager,
+    };
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+    use twilight_model::id::UserId;
+
+    fn stats(players: u64, playing_players: u64, system_load: f64) -> Stats {
+        Stats {
+            cpu: StatsCpu {
+                cores: 1,
+                lavalink_load: 0f64,
+                system_load,
+            },
+            frames: None,
+            memory: StatsMemory {
+                allocated: 0,
+                free: 0,
+                used: 0,
+                reservable: 0,
+            },
+            players,
+            playing_players,
+            op: Opcode::Stats,
+            uptime: 0,
+        }
+    }
+
+    /// Create a candidate of a node that never connects.
+    async fn candidate(regions: &[&str], stats: Stats) -> (SocketAddr, NodeCandidate) {
+        // Nothing listens on the port once the listener is dropped.
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut config = NodeConfig::new(UserId(1), address, "", None);
+        config.regions = regions.iter().map(|region| (*region).to_owned()).collect();
+        let (node, _) = Node::connect(config, PlayerManager::new()).await.unwrap();
+
+        (address, NodeCandidate { node, stats })
+    }
+
+    async fn candidates(players: &[u64]) -> Vec<NodeCandidate> {
+        let mut candidates = Vec::new();
+
+        for players in players {
+            candidates.push(candidate(&[], stats(*players, 0, 0f64)).await.1);
+        }
+
+        candidates
+    }
+
+    #[tokio::test]
+    async fn test_least_players() {
+        assert_eq!(Some(1), LeastPlayers.select(&candidates(&[5, 2, 7]).await));
+        assert_eq!(None, LeastPlayers.select(&[]));
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let selector = RoundRobin::new();
+        let candidates = candidates(&[0, 0, 0]).await;
+
+        assert_eq!(Some(0), selector.select(&candidates));
+        assert_eq!(Some(1), selector.select(&candidates));
+        assert_eq!(Some(2), selector.select(&candidates));
+        assert_eq!(Some(0), selector.select(&candidates));
+
+        // The turn carries over when the number of candidates changes.
+        assert_eq!(Some(0), selector.select(&candidates[..2]));
+        assert_eq!(None, selector.select(&[]));
+    }
+
+    #[tokio::test]
+    async fn test_weighted_capacity() {
+        let (_, half) = candidate(&[], stats(5, 0, 0f64)).await;
+        let (large, large_candidate) = candidate(&[], stats(8, 0, 0f64)).await;
+        let (empty, empty_candidate) = candidate(&[], stats(0, 0, 0f64)).await;
+
+        let selector = WeightedCapacity::new(10)
+            .capacity(large, 20)
+            .capacity(empty, 0);
+
+        // 8 of 20 is less than 5 of 10, and nodes without capacity are never
+        // selected.
+        let candidates = vec![half, large_candidate, empty_candidate.clone()];
+        assert_eq!(Some(1), selector.select(&candidates));
+        assert_eq!(None, selector.select(&[empty_candidate]));
+    }
+
+    #[tokio::test]
+    async fn test_weighted_penalty() {
+        let (_, busy) = candidate(&[], stats(10, 10, 0f64)).await;
+        let (_, loaded) = candidate(&[], stats(2, 2, 0.5)).await;
+        let candidates = vec![busy, loaded];
+
+        // A system load of 50% weighs more than 8 more playing players.
+        assert_eq!(Some(0), WeightedPenalty::default().select(&candidates));
+
+        let selector = WeightedPenalty {
+            system_load: 0f64,
+            ..WeightedPenalty::default()
+        };
+        assert_eq!(Some(1), selector.select(&candidates));
+    }
+
+    #[tokio::test]
+    async fn test_region_aware() {
+        let (_, europe) = candidate(&["Europe"], stats(5, 0, 0f64)).await;
+        let (_, us) = candidate(&["us"], stats(2, 0, 0f64)).await;
+        let (_, other) = candidate(&[], stats(3, 0, 0f64)).await;
+        let candidates = vec![europe, us, other];
+
+        let selector = RegionAware::new(LeastPlayers).region("rotterdam", "europe");
+
+        assert_eq!(
+            Some(0),
+            selector.select_for(&candidates, Some("rotterdam123.discord.media:443"))
+        );
+        assert_eq!(
+            Some(1),
+            selector.select_for(&candidates, Some("us1.discord.media:443"))
+        );
+
+        // Without a node in the region or a known voice server, any node may
+        // be selected.
+        assert_eq!(
+            Some(1),
+            selector.select_for(&candidates, Some("sydney5.discord.media:443"))
+        );
+        assert_eq!(Some(1), selector.select_for(&candidates, None));
+    }
+}
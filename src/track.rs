@@ -0,0 +1,630 @@
+//! Decode and encode base64 track strings without a node.
+//!
+//! Track strings are base64 encoded lavaplayer messages, written in the format
+//! of Java's `DataOutput`. This module supports message versions 1 through 3,
+//! allowing track information to be read without using the [`decode_track`]
+//! HTTP endpoint, and [`Play`] events to be created from stored track
+//! information.
+//!
+//! [`Play`]: ../model/outgoing/struct.Play.html
+//! [`decode_track`]: ../http/fn.decode_track.html
+
+use crate::http::TrackInfo;
+use base64::DecodeError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    string::FromUtf16Error,
+};
+
+/// The flag in a message header that indicates a version byte is present.
+const TRACK_INFO_VERSIONED: u32 = 1;
+
+/// The latest supported message version.
+const TRACK_INFO_VERSION: u8 = 3;
+
+/// Known lavaplayer source names and the class names of their tracks.
+const SOURCES: &[(&str, &str)] = &[
+    (
+        "bandcamp",
+        "com.sedmelluq.discord.lavaplayer.source.bandcamp.BandcampAudioTrack",
+    ),
+    (
+        "beam",
+        "com.sedmelluq.discord.lavaplayer.source.beam.BeamAudioTrack",
+    ),
+    (
+        "getyarn.io",
+        "com.sedmelluq.discord.lavaplayer.source.getyarn.GetyarnAudioTrack",
+    ),
+    (
+        "http",
+        "com.sedmelluq.discord.lavaplayer.source.http.HttpAudioTrack",
+    ),
+    (
+        "local",
+        "com.sedmelluq.discord.lavaplayer.source.local.LocalAudioTrack",
+    ),
+    (
+        "soundcloud",
+        "com.sedmelluq.discord.lavaplayer.source.soundcloud.SoundCloudAudioTrack",
+    ),
+    (
+        "twitch",
+        "com.sedmelluq.discord.lavaplayer.source.twitch.TwitchStreamAudioTrack",
+    ),
+    (
+        "vimeo",
+        "com.sedmelluq.discord.lavaplayer.source.vimeo.VimeoAudioTrack",
+    ),
+    (
+        "youtube",
+        "com.sedmelluq.discord.lavaplayer.source.youtube.YoutubeAudioTrack",
+    ),
+];
+
+/// An error that can occur while decoding a track string.
+#[derive(Debug)]
+pub enum TrackError {
+    /// The track string isn't valid base64.
+    DecodingBase64 {
+        /// The source of the error from the `base64` crate.
+        source: DecodeError,
+    },
+    /// A string within the track isn't valid modified UTF-8.
+    DecodingString {
+        /// The source of the error from `std`.
+        source: FromUtf16Error,
+    },
+    /// The track ended before all of its fields were read.
+    UnexpectedEnd,
+    /// The track was written with a message version that isn't supported.
+    UnsupportedVersion {
+        /// The version of the message.
+        version: u8,
+    },
+}
+
+impl Display for TrackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::DecodingBase64 { .. } => f.write_str("track is not valid base64"),
+            Self::DecodingString { .. } => f.write_str("track contains an invalid string"),
+            Self::UnexpectedEnd => f.write_str("track ended unexpectedly"),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "track version {} is not supported", version)
+            }
+        }
+    }
+}
+
+impl Error for TrackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DecodingBase64 { source } => Some(source),
+            Self::DecodingString { source } => Some(source),
+            Self::UnexpectedEnd => None,
+            Self::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+/// All of the information contained within a track string.
+///
+/// Unlike [`TrackInfo`], this keeps the fields that lavaplayer needs to load
+/// some tracks, so that decoding and then encoding a track results in the same
+/// track string.
+///
+/// [`TrackInfo`]: ../http/struct.TrackInfo.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackBlob {
+    /// The version of the message.
+    pub version: u8,
+    /// Details about the track, such as the author and title.
+    pub info: TrackInfo,
+    /// The name of the source manager that loaded the track.
+    pub source: String,
+    /// The URL of the track's artwork, only present in version 3.
+    pub artwork_url: Option<String>,
+    /// The ISRC of the track, only present in version 3.
+    pub isrc: Option<String>,
+    /// Data specific to the source manager, such as the container of a local
+    /// track.
+    pub source_data: Vec<u8>,
+}
+
+impl TrackBlob {
+    /// Decode a base64 track string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TrackError::DecodingBase64`] if the string isn't valid base64.
+    ///
+    /// Returns [`TrackError::DecodingString`] if a string field isn't valid
+    /// modified UTF-8.
+    ///
+    /// Returns [`TrackError::UnexpectedEnd`] if the track is truncated.
+    ///
+    /// Returns [`TrackError::UnsupportedVersion`] if the message version is
+    /// newer than 3.
+    ///
+    /// [`TrackError::DecodingBase64`]: enum.TrackError.html#variant.DecodingBase64
+    /// [`TrackError::DecodingString`]: enum.TrackError.html#variant.DecodingString
+    /// [`TrackError::UnexpectedEnd`]: enum.TrackError.html#variant.UnexpectedEnd
+    /// [`TrackError::UnsupportedVersion`]: enum.TrackError.html#variant.UnsupportedVersion
+    pub fn decode(track: impl AsRef<str>) -> Result<Self, TrackError> {
+        let bytes = base64::decode(track.as_ref())
+            .map_err(|source| TrackError::DecodingBase64 { source })?;
+        let mut reader = Reader::new(&bytes);
+
+        let header = reader.read_u32()?;
+        let flags = header >> 30;
+        let size = (header & 0x3FFF_FFFF) as usize;
+        let mut reader = Reader::new(reader.take(size)?);
+
+        let version = if flags & TRACK_INFO_VERSIONED != 0 {
+            reader.read_u8()?
+        } else {
+            1
+        };
+
+        if version == 0 || version > TRACK_INFO_VERSION {
+            return Err(TrackError::UnsupportedVersion { version });
+        }
+
+        let title = reader.read_utf()?;
+        let author = reader.read_utf()?;
+        let length = reader.read_u64()?;
+        let identifier = reader.read_utf()?;
+        let is_stream = reader.read_bool()?;
+        let uri = if version >= 2 {
+            reader.read_nullable_utf()?
+        } else {
+            None
+        };
+        let (artwork_url, isrc) = if version >= 3 {
+            (reader.read_nullable_utf()?, reader.read_nullable_utf()?)
+        } else {
+            (None, None)
+        };
+        let source = reader.read_utf()?;
+
+        // The source data has no length of its own, and is followed only by
+        // the position.
+        let remaining = reader.remaining();
+        let source_data = reader
+            .take(remaining.checked_sub(8).ok_or(TrackError::UnexpectedEnd)?)?
+            .to_vec();
+        let position = reader.read_u64()?;
+
+        let class = SOURCES
+            .iter()
+            .find(|(name, _)| *name == source)
+            .map_or_else(|| source.clone(), |(_, class)| (*class).to_owned());
+
+        Ok(Self {
+            version,
+            info: TrackInfo {
+                class,
+                title,
+                author,
+                length,
+                identifier,
+                uri: uri.unwrap_or_default(),
+                is_stream,
+                is_seekable: !is_stream,
+                position,
+            },
+            source,
+            artwork_url,
+            isrc,
+            source_data,
+        })
+    }
+
+    /// Encode the track as a base64 track string.
+    pub fn encode(&self) -> String {
+        let mut writer = Writer::default();
+
+        writer.write_u8(self.version);
+        writer.write_utf(&self.info.title);
+        writer.write_utf(&self.info.author);
+        writer.write_u64(self.info.length);
+        writer.write_utf(&self.info.identifier);
+        writer.write_bool(self.info.is_stream);
+
+        if self.version >= 2 {
+            let uri = Some(self.info.uri.as_str()).filter(|uri| !uri.is_empty());
+            writer.write_nullable_utf(uri);
+        }
+
+        if self.version >= 3 {
+            writer.write_nullable_utf(self.artwork_url.as_deref());
+            writer.write_nullable_utf(self.isrc.as_deref());
+        }
+
+        writer.write_utf(&self.source);
+        writer.0.extend_from_slice(&self.source_data);
+        writer.write_u64(self.info.position);
+
+        let header = (TRACK_INFO_VERSIONED << 30) | writer.0.len() as u32;
+        let mut bytes = header.to_be_bytes().to_vec();
+        bytes.extend(writer.0);
+
+        base64::encode(bytes)
+    }
+}
+
+impl From<TrackInfo> for TrackBlob {
+    fn from(info: TrackInfo) -> Self {
+        let source = SOURCES
+            .iter()
+            .find(|(_, class)| *class == info.class)
+            .map_or_else(|| info.class.clone(), |(name, _)| (*name).to_owned());
+
+        Self {
+            version: 2,
+            info,
+            source,
+            artwork_url: None,
+            isrc: None,
+            source_data: Vec::new(),
+        }
+    }
+}
+
+/// Decode the information of a base64 track string.
+///
+/// Refer to [`TrackBlob::decode`] for the possible errors.
+///
+/// # Examples
+///
+/// Encode track information and decode it again:
+///
+/// ```
+/// use twilight_andesite::{http::TrackInfo, track};
+///
+/// let info = TrackInfo {
+///     class: "com.sedmelluq.discord.lavaplayer.source.youtube.YoutubeAudioTrack".to_owned(),
+///     title: "Rick Astley - Never Gonna Give You Up".to_owned(),
+///     author: "RickAstleyVEVO".to_owned(),
+///     length: 212_000,
+///     identifier: "dQw4w9WgXcQ".to_owned(),
+///     uri: "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_owned(),
+///     is_stream: false,
+///     is_seekable: true,
+///     position: 0,
+/// };
+///
+/// let encoded = track::encode(info.clone());
+/// assert_eq!(track::decode(encoded)?, info);
+/// # Ok::<_, track::TrackError>(())
+/// ```
+///
+/// [`TrackBlob::decode`]: struct.TrackBlob.html#method.decode
+pub fn decode(track: impl AsRef<str>) -> Result<TrackInfo, TrackError> {
+    TrackBlob::decode(track).map(|blob| blob.info)
+}
+
+/// Encode track information as a base64 track string.
+///
+/// Tracks from sources that require additional data, such as HTTP and local
+/// tracks, can't be loaded from an encoding of only their information. Keep
+/// the [`TrackBlob`] of these tracks instead.
+///
+/// [`TrackBlob`]: struct.TrackBlob.html
+pub fn encode(info: TrackInfo) -> String {
+    TrackBlob::from(info).encode()
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+
+    fn remaining(&self) -> usize {
+        self.0.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TrackError> {
+        if self.0.len() < len {
+            return Err(TrackError::UnexpectedEnd);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(taken)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, TrackError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_bool(&mut self) -> Result<bool, TrackError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, TrackError> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.take(2)?);
+
+        Ok(u16::from_be_bytes(bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, TrackError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, TrackError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    /// Read a string written by Java's `DataOutput::writeUTF`, which uses
+    /// modified UTF-8.
+    fn read_utf(&mut self) -> Result<String, TrackError> {
+        let len = self.read_u16()? as usize;
+        let mut bytes = self.take(len)?.iter().map(|byte| u16::from(*byte));
+        let mut units = Vec::with_capacity(len);
+
+        while let Some(first) = bytes.next() {
+            let unit = match first {
+                0x00..=0x7F => first,
+                0xC0..=0xDF => {
+                    let second = bytes.next().ok_or(TrackError::UnexpectedEnd)?;
+
+                    ((first & 0x1F) << 6) | (second & 0x3F)
+                }
+                _ => {
+                    let second = bytes.next().ok_or(TrackError::UnexpectedEnd)?;
+                    let third = bytes.next().ok_or(TrackError::UnexpectedEnd)?;
+
+                    ((first & 0x0F) << 12) | ((second & 0x3F) << 6) | (third & 0x3F)
+                }
+            };
+
+            units.push(unit);
+        }
+
+        String::from_utf16(&units).map_err(|source| TrackError::DecodingString { source })
+    }
+
+    fn read_nullable_utf(&mut self) -> Result<Option<String>, TrackError> {
+        if self.read_bool()? {
+            self.read_utf().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn write_u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Write a string the same way as Java's `DataOutput::writeUTF`, which
+    /// uses modified UTF-8.
+    fn write_utf(&mut self, value: &str) {
+        let mut bytes = Vec::with_capacity(value.len());
+
+        for unit in value.encode_utf16() {
+            match unit {
+                0x01..=0x7F => bytes.push(unit as u8),
+                0x00 | 0x80..=0x07FF => {
+                    bytes.push(0xC0 | (unit >> 6) as u8);
+                    bytes.push(0x80 | (unit & 0x3F) as u8);
+                }
+                _ => {
+                    bytes.push(0xE0 | (unit >> 12) as u8);
+                    bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                    bytes.push(0x80 | (unit & 0x3F) as u8);
+                }
+            }
+        }
+
+        // Java refuses to write longer strings, so truncate rather than
+        // writing a length that doesn't match.
+        bytes.truncate(u16::MAX as usize);
+
+        self.0
+            .extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+        self.0.extend(bytes);
+    }
+
+    fn write_nullable_utf(&mut self, value: Option<&str>) {
+        self.write_bool(value.is_some());
+
+        if let Some(value) = value {
+            self.write_utf(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TrackBlob, TrackError};
+    use crate::http::TrackInfo;
+
+    /// A track string of version 2, from the Lavalink documentation.
+    const TRACK_V2: &str = "QAAAjQIAJVJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAADlJpY2tBc3RsZXlWRVZPAAAAAAADPCAAC2RRdzR3OVdnWGNRAAEAK2h0dHBzOi8vd3d3LnlvdXR1YmUuY29tL3dhdGNoP3Y9ZFF3NHc5V2dYY1EAB3lvdXR1YmUAAAAAAAAAAA==";
+
+    /// A track string of version 3, with an artwork URL but no ISRC.
+    const TRACK_V3: &str = "QAAA1QMAPFJpY2sgQXN0bGV5IC0gTmV2ZXIgR29ubmEgR2l2ZSBZb3UgVXAgKE9mZmljaWFsIE11c2ljIFZpZGVvKQALUmljayBBc3RsZXkAAAAAAANACAALZFF3NHc5V2dYY1EAAQAraHR0cHM6Ly93d3cueW91dHViZS5jb20vd2F0Y2g/dj1kUXc0dzlXZ1hjUQEAMGh0dHBzOi8vaS55dGltZy5jb20vdmkvZFF3NHc5V2dYY1EvaHFkZWZhdWx0LmpwZwAAB3lvdXR1YmUAAAAAAAAAAA==";
+
+    const YOUTUBE: &str = "com.sedmelluq.discord.lavaplayer.source.youtube.YoutubeAudioTrack";
+
+    /// A message of version 1 from the HTTP source, which has no version byte.
+    fn track_v1() -> String {
+        let message: &[u8] = &[
+            b"\x00\x05title".as_ref(),
+            b"\x00\x06author",
+            &1000_u64.to_be_bytes(),
+            b"\x00\x19https://example.com/a.mp3",
+            b"\x00",
+            b"\x00\x04http",
+            b"\x00\x03mp3",
+            &500_u64.to_be_bytes(),
+        ]
+        .concat();
+
+        let mut bytes = (message.len() as u32).to_be_bytes().to_vec();
+        bytes.extend(message);
+
+        base64::encode(bytes)
+    }
+
+    #[test]
+    fn test_decode_v1() -> Result<(), TrackError> {
+        let blob = TrackBlob::decode(track_v1())?;
+
+        assert_eq!(1, blob.version);
+        assert_eq!(
+            TrackInfo {
+                class: "com.sedmelluq.discord.lavaplayer.source.http.HttpAudioTrack".to_owned(),
+                title: "title".to_owned(),
+                author: "author".to_owned(),
+                length: 1000,
+                identifier: "https://example.com/a.mp3".to_owned(),
+                uri: String::new(),
+                is_stream: false,
+                is_seekable: true,
+                position: 500,
+            },
+            blob.info
+        );
+        assert_eq!("http", blob.source);
+        assert_eq!(b"\x00\x03mp3", blob.source_data.as_slice());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_v2() -> Result<(), TrackError> {
+        let blob = TrackBlob::decode(TRACK_V2)?;
+
+        assert_eq!(2, blob.version);
+        assert_eq!(
+            TrackInfo {
+                class: YOUTUBE.to_owned(),
+                title: "Rick Astley - Never Gonna Give You Up".to_owned(),
+                author: "RickAstleyVEVO".to_owned(),
+                length: 212_000,
+                identifier: "dQw4w9WgXcQ".to_owned(),
+                uri: "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_owned(),
+                is_stream: false,
+                is_seekable: true,
+                position: 0,
+            },
+            blob.info
+        );
+        assert_eq!("youtube", blob.source);
+        assert!(blob.artwork_url.is_none());
+        assert!(blob.isrc.is_none());
+        assert!(blob.source_data.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_v3() -> Result<(), TrackError> {
+        let blob = TrackBlob::decode(TRACK_V3)?;
+
+        assert_eq!(3, blob.version);
+        assert_eq!(YOUTUBE, blob.info.class);
+        assert_eq!(
+            "Rick Astley - Never Gonna Give You Up (Official Music Video)",
+            blob.info.title
+        );
+        assert_eq!("Rick Astley", blob.info.author);
+        assert_eq!(213_000, blob.info.length);
+        assert_eq!(
+            Some("https://i.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg"),
+            blob.artwork_url.as_deref()
+        );
+        assert!(blob.isrc.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), TrackError> {
+        for track in &[TRACK_V2, TRACK_V3] {
+            assert_eq!(*track, TrackBlob::decode(track)?.encode());
+        }
+
+        // Encoding always writes a version byte, so only the decoded track
+        // stays the same.
+        let blob = TrackBlob::decode(track_v1())?;
+        assert_eq!(blob, TrackBlob::decode(blob.encode())?);
+
+        let info = super::decode(TRACK_V2)?;
+        assert_eq!(TRACK_V2, super::encode(info));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncated() {
+        let bytes = base64::decode(TRACK_V3).unwrap();
+
+        for len in 0..bytes.len() {
+            let track = base64::encode(&bytes[..len]);
+
+            assert!(matches!(
+                TrackBlob::decode(track),
+                Err(TrackError::UnexpectedEnd)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            TrackBlob::decode("not base64!"),
+            Err(TrackError::DecodingBase64 { .. })
+        ));
+
+        let mut bytes = base64::decode(TRACK_V2).unwrap();
+        bytes[4] = 4;
+        assert!(matches!(
+            TrackBlob::decode(base64::encode(&bytes)),
+            Err(TrackError::UnsupportedVersion { version: 4 })
+        ));
+
+        // A header that claims more bytes than the track has.
+        let mut bytes = base64::decode(TRACK_V2).unwrap();
+        bytes[0] = 0x7F;
+        assert!(matches!(
+            TrackBlob::decode(base64::encode(&bytes)),
+            Err(TrackError::UnexpectedEnd)
+        ));
+
+        // A lone surrogate in the title.
+        let mut bytes = base64::decode(TRACK_V2).unwrap();
+        bytes[7..10].copy_from_slice(b"\xED\xA0\x80");
+        assert!(matches!(
+            TrackBlob::decode(base64::encode(&bytes)),
+            Err(TrackError::DecodingString { .. })
+        ));
+    }
+}